@@ -0,0 +1,230 @@
+use common::cli::{load_input, set_source, Source};
+use common::{set_verbose, Error, Puzzle};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub const BENCH_USAGE: &str = "Usage: aoc bench <day|all> [input file|-] [bench options]
+
+Runs the parsing and both parts of each day several times and reports the
+min/median/max times. Medians can be saved to a baseline file and compared
+against it later; a median slower than the baseline by more than the
+threshold is reported as a regression.
+
+Bench options:
+    --input <text>          Use the given text as input instead of a file
+    --runs <N>              Number of runs of each step (default 10)
+    --baseline <file>       Compare medians against a saved baseline
+    --save <file>           Save the medians as a new baseline
+    --threshold <percent>   Allowed slowdown against the baseline (default 10)";
+
+pub struct BenchOptions {
+    pub source: Option<Source>,
+    pub runs: usize,
+    pub baseline: Option<String>,
+    pub save: Option<String>,
+    pub threshold: f64,
+}
+
+pub fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions {
+        source: None,
+        runs: 10,
+        baseline: None,
+        save: None,
+        threshold: 10.0,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--runs" => {
+                    options.runs = match value.parse::<usize>() {
+                        Ok(runs) if runs > 0 => runs,
+                        _ => return Err(format!("Invalid number of runs: {}", value)),
+                    }
+                }
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                "--baseline" => options.baseline = Some(value.clone()),
+                "--save" => options.save = Some(value.clone()),
+                "--threshold" => {
+                    options.threshold = value
+                        .parse::<f64>()
+                        .map_err(|_| format!("Invalid threshold: {}", value))?
+                }
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+/// Timings of one step of a day, sorted from fastest to slowest.
+struct Timings {
+    day: u32,
+    step: &'static str,
+    times: Vec<Duration>,
+}
+
+impl Timings {
+    fn min(&self) -> Duration {
+        self.times[0]
+    }
+
+    fn median(&self) -> Duration {
+        self.times[self.times.len() / 2]
+    }
+
+    fn max(&self) -> Duration {
+        self.times[self.times.len() - 1]
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {:>2} {:<5}  min {:>10.3}ms  median {:>10.3}ms  max {:>10.3}ms",
+            self.day,
+            self.step,
+            ms(self.min()),
+            ms(self.median()),
+            ms(self.max())
+        )
+    }
+}
+
+fn time_step<T>(day: u32, step: &'static str, runs: usize, f: impl Fn() -> T) -> Timings {
+    let mut times = (0..runs)
+        .map(|_| {
+            let instant = Instant::now();
+            black_box(f());
+            instant.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+    times.sort();
+    Timings { day, step, times }
+}
+
+fn bench_day(puzzle: &Puzzle, options: &BenchOptions) -> Result<Vec<Timings>, Error> {
+    let source = match (&options.source, puzzle.builtin_input) {
+        (None, None) => Some(Source::File(format!("day{}/input.txt", puzzle.day))),
+        (source, _) => source.clone(),
+    };
+    let (input, name) = load_input(puzzle, &source)?;
+    // Check the input once, so the timed runs can't fail
+    (puzzle.parse)(&input).map_err(|err| err.in_file(&name))?;
+    Ok(vec![
+        time_step(puzzle.day, "parse", options.runs, || (puzzle.parse)(&input)),
+        time_step(puzzle.day, "part1", options.runs, || (puzzle.part1)(&input)),
+        time_step(puzzle.day, "part2", options.runs, || (puzzle.part2)(&input)),
+    ])
+}
+
+/// Medians in nanoseconds by day and step.
+type Baseline = HashMap<(u32, String), u64>;
+
+fn parse_baseline(text: &str) -> Result<Baseline, String> {
+    let mut baseline = Baseline::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parts = line.split_whitespace().collect::<Vec<&str>>();
+        let entry = match parts.as_slice() {
+            [day, step, nanos] => day
+                .parse::<u32>()
+                .ok()
+                .zip(nanos.parse::<u64>().ok())
+                .map(|(day, nanos)| ((day, step.to_string()), nanos)),
+            _ => None,
+        };
+        match entry {
+            Some((key, nanos)) => baseline.insert(key, nanos),
+            None => return Err(format!("line {}: expected '<day> <step> <nanos>'", idx + 1)),
+        };
+    }
+    Ok(baseline)
+}
+
+fn format_baseline(timings: &[Timings]) -> String {
+    let mut text = "# day step median_ns\n".to_string();
+    for t in timings.iter() {
+        text += &format!("{} {} {}\n", t.day, t.step, t.median().as_nanos());
+    }
+    text
+}
+
+/// Benchmarks the puzzles and returns true if any of them regressed against
+/// the baseline.
+pub fn run_bench(puzzles: &[&Puzzle], options: &BenchOptions) -> Result<bool, String> {
+    let baseline = match &options.baseline {
+        Some(file) => {
+            let text = fs::read_to_string(file).map_err(|err| format!("{}: {}", file, err))?;
+            Some(parse_baseline(&text).map_err(|err| format!("{}: {}", file, err))?)
+        }
+        None => None,
+    };
+    set_verbose(false);
+    let mut all_timings = Vec::new();
+    let mut regressed = false;
+    for puzzle in puzzles.iter() {
+        let timings = match bench_day(puzzle, options) {
+            Ok(timings) => timings,
+            Err(err) => return Err(format!("day {}: {}", puzzle.day, err)),
+        };
+        for t in timings.iter() {
+            let key = (t.day, t.step.to_string());
+            match baseline.as_ref().and_then(|b| b.get(&key)) {
+                Some(&nanos) => {
+                    let change = (t.median().as_nanos() as f64 / nanos.max(1) as f64 - 1.0) * 100.0;
+                    if change > options.threshold {
+                        regressed = true;
+                        println!("{}  {:+.1}% REGRESSION", t, change);
+                    } else {
+                        println!("{}  {:+.1}%", t, change);
+                    }
+                }
+                None => println!("{}", t),
+            }
+        }
+        all_timings.extend(timings);
+    }
+    if let Some(file) = &options.save {
+        fs::write(file, format_baseline(&all_timings))
+            .map_err(|err| format!("{}: {}", file, err))?;
+        println!("Saved baseline to {}", file);
+    }
+    Ok(regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_round_trip() {
+        let timings = vec![Timings {
+            day: 17,
+            step: "part2",
+            times: vec![
+                Duration::from_nanos(10),
+                Duration::from_nanos(20),
+                Duration::from_nanos(40),
+            ],
+        }];
+        let baseline = parse_baseline(&format_baseline(&timings)).unwrap();
+        assert_eq!(baseline.get(&(17, "part2".to_string())), Some(&20));
+        assert!(parse_baseline("17 part2").is_err());
+    }
+}
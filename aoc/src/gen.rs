@@ -0,0 +1,158 @@
+use common::{set_verbose, Generated, Puzzle, Rng};
+
+pub const GEN_USAGE: &str = "Usage: aoc gen <day|all> [gen options]
+
+Prints a random input for a day, which can be piped into aoc run or
+aoc bench with - as the input file. The same seed and size always give the
+same input. With --check the input is solved instead, and the answers are
+compared against the ones known from how the input was generated.
+
+Gen options:
+    --size <N>              Size of the input, roughly the number of records
+                            (default: about the size of the real input);
+                            days 5 and 20 can't make inputs above 1000 seats
+                            and 144 tiles
+    --seed <N>              Seed for the random numbers (default 1)
+    --check                 Solve the input and check the known answers";
+
+pub struct GenOptions {
+    pub size: Option<usize>,
+    pub seed: u64,
+    pub check: bool,
+}
+
+pub fn parse_gen_options(args: &[String]) -> Result<GenOptions, String> {
+    let mut options = GenOptions {
+        size: None,
+        seed: 1,
+        check: false,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--check" => options.check = true,
+            "--size" | "--seed" => {
+                let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+                let n = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid number for {}: {}", arg, value))?;
+                if arg == "--size" {
+                    options.size = Some(n as usize);
+                } else {
+                    options.seed = n;
+                }
+            }
+            _ => return Err(format!("Unknown option: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Generates an input, or returns an error if the size is more than the
+/// day's generator can make.
+pub fn generate(puzzle: &Puzzle, options: &GenOptions) -> Result<Generated, String> {
+    let mut rng = Rng::new(options.seed);
+    let size = options.size.unwrap_or(puzzle.generator.default_size);
+    match puzzle.generator.max_size {
+        Some(max) if size > max => Err(format!("the size can be at most {}", max)),
+        _ => Ok((puzzle.generator.generate)(&mut rng, size)),
+    }
+}
+
+/// Solves a generated input and compares the answers with the known ones.
+/// Returns a line describing the result, or an error if the input couldn't
+/// be solved or an answer was wrong.
+pub fn check(puzzle: &Puzzle, generated: &Generated) -> Result<String, String> {
+    let parts = [
+        (1, puzzle.part1, generated.part1),
+        (2, puzzle.part2, generated.part2),
+    ];
+    let mut results = Vec::new();
+    for (part, solve, expected) in parts.iter() {
+        let answer = solve(&generated.input)
+            .map_err(|err| format!("part {}: {}", part, err.in_file("<generated>")))?;
+        match expected {
+            Some(expected) if *expected != answer => {
+                return Err(format!(
+                    "part {}: expected {}, found {}",
+                    part, expected, answer
+                ))
+            }
+            Some(_) => results.push(format!("part {} ok ({})", part, answer)),
+            None => results.push(format!("part {} {}", part, answer)),
+        }
+    }
+    Ok(results.join(", "))
+}
+
+/// Generates inputs for the puzzles, and either prints them or checks them.
+/// Returns false if any check failed.
+pub fn run_gen(puzzles: &[&Puzzle], options: &GenOptions) -> bool {
+    if !options.check {
+        for puzzle in puzzles.iter() {
+            match generate(puzzle, options) {
+                Ok(generated) => print!("{}", generated.input),
+                Err(err) => {
+                    eprintln!("error: day {}: {}", puzzle.day, err);
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+    set_verbose(false);
+    let mut ok = true;
+    for puzzle in puzzles.iter() {
+        match generate(puzzle, options).and_then(|generated| check(puzzle, &generated)) {
+            Ok(line) => println!("Day {}: {}", puzzle.day, line),
+            Err(err) => {
+                eprintln!("error: day {}: {}", puzzle.day, err);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_answers_match() {
+        for puzzle in crate::DAYS.iter() {
+            for seed in 1..4 {
+                let options = GenOptions {
+                    size: Some(puzzle.generator.default_size.min(5)),
+                    seed,
+                    check: true,
+                };
+                let generated = generate(puzzle, &options).unwrap();
+                assert_eq!(generated.input, generate(puzzle, &options).unwrap().input);
+                // Solving is slow for some days whatever the input, so the
+                // others are only parsed
+                let result = if generated.part1.is_some() || generated.part2.is_some() {
+                    check(puzzle, &generated)
+                } else {
+                    (puzzle.parse)(&generated.input)
+                        .map(|_| String::new())
+                        .map_err(|err| err.to_string())
+                };
+                if let Err(err) = result {
+                    panic!("day {} seed {}: {}", puzzle.day, seed, err);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_above_the_limit() {
+        let options = GenOptions {
+            size: Some(145),
+            seed: 1,
+            check: false,
+        };
+        let err = generate(&day20::PUZZLE, &options).err();
+        assert_eq!(err.as_deref(), Some("the size can be at most 144"));
+    }
+}
@@ -0,0 +1,171 @@
+mod bench;
+mod gen;
+
+use bench::{parse_bench_options, run_bench, BenchOptions, BENCH_USAGE};
+use common::cli::{
+    load_input, parse_options, print_input, print_solutions, run_puzzle, solve_puzzle, Options,
+    Source, OPTIONS_USAGE,
+};
+use common::{par, set_verbose, Puzzle};
+use gen::{parse_gen_options, run_gen, GenOptions, GEN_USAGE};
+use std::env;
+use std::process;
+
+const DAYS: &[Puzzle] = &[
+    day3::PUZZLE,
+    day4::PUZZLE,
+    day5::PUZZLE,
+    day6::PUZZLE,
+    day7::PUZZLE,
+    day8::PUZZLE,
+    day9::PUZZLE,
+    day10::PUZZLE,
+    day14::PUZZLE,
+    day15::PUZZLE,
+    day16::PUZZLE,
+    day17::PUZZLE,
+    day18::PUZZLE,
+    day19::PUZZLE,
+    day20::PUZZLE,
+];
+
+const USAGE: &str = "Usage: aoc run <day|all> [input file|-] [options]
+
+Runs the solutions for one day, or for all days. When no input file is
+given, day<N>/input.txt (or the day's built in input) is used.";
+
+enum Command {
+    Run(Options),
+    Bench(BenchOptions),
+    Gen(GenOptions),
+}
+
+fn parse_args(args: &[String]) -> Result<(Option<u32>, Command), String> {
+    let cmd = match args.first() {
+        Some(cmd) if ["run", "bench", "gen"].contains(&cmd.as_str()) => cmd,
+        Some(cmd) => return Err(format!("Unknown command: {}", cmd)),
+        None => return Err("No command specified".to_string()),
+    };
+    let day = match args.get(1).map(|a| a.as_str()) {
+        Some("all") => None,
+        Some(day) => Some(
+            day.parse::<u32>()
+                .map_err(|_| format!("Invalid day: {}", day))?,
+        ),
+        None => return Err("No day specified".to_string()),
+    };
+    let command = match cmd.as_str() {
+        "run" => Command::Run(parse_options(&args[2..])?),
+        "bench" => Command::Bench(parse_bench_options(&args[2..])?),
+        _ => {
+            let options = parse_gen_options(&args[2..])?;
+            if day.is_none() && !options.check {
+                return Err("Inputs for all days can only be checked, not printed".to_string());
+            }
+            Command::Gen(options)
+        }
+    };
+    Ok((day, command))
+}
+
+/// Stdin can only be read once, so when running several days it is read up
+/// front and passed on as text.
+fn share_stdin(days: &[&Puzzle], source: &mut Option<Source>) {
+    if days.len() > 1 && *source == Some(Source::Stdin) {
+        match load_input(days[0], source) {
+            Ok((input, _)) => *source = Some(Source::Text(input)),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn day_options(puzzle: &Puzzle, options: &Options) -> Options {
+    if options.source.is_none() && puzzle.builtin_input.is_none() {
+        Options {
+            source: Some(Source::File(format!("day{}/input.txt", puzzle.day))),
+            ..options.clone()
+        }
+    } else {
+        options.clone()
+    }
+}
+
+/// Runs the days one after another, or all at once with the `parallel`
+/// feature. The answers are printed in the same order either way, but debug
+/// output is turned off in parallel since it would get mixed up. Returns false
+/// if any day failed.
+fn run_days(days: &[&Puzzle], options: &Options) -> bool {
+    let days = days
+        .iter()
+        .map(|puzzle| (*puzzle, day_options(puzzle, options)))
+        .collect::<Vec<(&Puzzle, Options)>>();
+    let mut ok = true;
+    if cfg!(feature = "parallel") && days.len() > 1 {
+        set_verbose(false);
+        let results = par::map(&days, |(puzzle, options)| solve_puzzle(puzzle, options));
+        for ((puzzle, options), results) in days.iter().zip(results) {
+            print_input(puzzle, options);
+            if let Err(err) = print_solutions(puzzle, options, results) {
+                eprintln!("error: day {}: {}", puzzle.day, err);
+                ok = false;
+            }
+        }
+    } else {
+        for (puzzle, options) in days.iter() {
+            if let Err(err) = run_puzzle(puzzle, options) {
+                eprintln!("error: day {}: {}", puzzle.day, err);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (day, command) = match parse_args(&args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!(
+                "{}\n\n{}\n\n{}\n\n{}\n\n{}",
+                err, USAGE, OPTIONS_USAGE, BENCH_USAGE, GEN_USAGE
+            );
+            process::exit(2);
+        }
+    };
+    let days = DAYS
+        .iter()
+        .filter(|d| day.is_none() || day == Some(d.day))
+        .collect::<Vec<&Puzzle>>();
+    if days.is_empty() {
+        eprintln!("No solution for day {}", day.unwrap_or(0));
+        process::exit(2);
+    }
+    let mut options = match command {
+        Command::Run(options) => options,
+        Command::Bench(mut options) => {
+            share_stdin(&days, &mut options.source);
+            match run_bench(&days, &options) {
+                Ok(false) => return,
+                Ok(true) => process::exit(1),
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Gen(options) => {
+            if !run_gen(&days, &options) {
+                process::exit(1);
+            }
+            return;
+        }
+    };
+    share_stdin(&days, &mut options.source);
+    if !run_days(&days, &options) {
+        process::exit(1);
+    }
+}
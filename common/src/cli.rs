@@ -0,0 +1,318 @@
+use crate::{read_file, set_verbose, Answer, Error, ParseError, Puzzle};
+use std::env;
+use std::io::{self, Read};
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(String),
+    Stdin,
+    Text(String),
+}
+
+impl Source {
+    /// Parses an input argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(arg.to_string())
+        }
+    }
+
+    /// The name of the input used in messages.
+    pub fn name(&self) -> &str {
+        match self {
+            Source::File(file) => file,
+            Source::Stdin => "<stdin>",
+            Source::Text(_) => "<input>",
+        }
+    }
+}
+
+/// Options shared by the day binaries and the `aoc` runner.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub source: Option<Source>,
+    pub part: Option<u32>,
+    pub format: Format,
+}
+
+pub const OPTIONS_USAGE: &str = "The input file can be - to read the input from stdin.
+
+Options:
+    --input <text>          Use the given text as input instead of a file
+    --part <1|2>            Only run one of the parts
+    --format <text|json>    Print answers as text (default) or JSON lines";
+
+/// Sets the input source, failing if one has already been given.
+pub fn set_source(source: &mut Option<Source>, new: Source) -> Result<(), String> {
+    match source {
+        Some(old) => Err(format!(
+            "More than one input given: {} and {}",
+            old.name(),
+            new.name()
+        )),
+        None => {
+            *source = Some(new);
+            Ok(())
+        }
+    }
+}
+
+pub fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        source: None,
+        part: None,
+        format: Format::Text,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg == "--part" {
+            let part = rest.next().ok_or("Missing value for --part")?;
+            match part.as_str() {
+                "1" => options.part = Some(1),
+                "2" => options.part = Some(2),
+                _ => return Err(format!("Invalid part: {}", part)),
+            }
+        } else if arg == "--format" {
+            let format = rest.next().ok_or("Missing value for --format")?;
+            options.format = format.parse()?;
+        } else if arg == "--input" {
+            let text = rest.next().ok_or("Missing value for --input")?;
+            set_source(&mut options.source, Source::Text(text.clone()))?;
+        } else if arg.starts_with("--") {
+            return Err(format!("Unknown option: {}", arg));
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+fn json_line(day: u32, part: u32, answer: Answer, elapsed: Duration) -> String {
+    let answer = match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::NotFound => "null".to_string(),
+    };
+    format!(
+        r#"{{"day":{},"part":{},"answer":{},"elapsed_ms":{:.3}}}"#,
+        day,
+        part,
+        answer,
+        elapsed.as_secs_f64() * 1000.0
+    )
+}
+
+fn print_answer(format: Format, day: u32, part: u32, answer: Answer, elapsed: Duration) {
+    match format {
+        Format::Text => println!("Day {} part {}: {}", day, part, answer),
+        Format::Json => println!("{}", json_line(day, part, answer, elapsed)),
+    }
+}
+
+/// Reads the input from `source`, or the puzzle's built in input if no source
+/// is given. Returns the input and a name for it to use in errors.
+pub fn load_input(puzzle: &Puzzle, source: &Option<Source>) -> Result<(String, String), Error> {
+    let input = match (source, puzzle.builtin_input) {
+        (Some(Source::File(file)), _) => read_file(file)?,
+        (Some(Source::Stdin), _) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| Error::Io("<stdin>".to_string(), err))?;
+            input
+        }
+        (Some(Source::Text(text)), _) => text.clone(),
+        (None, Some(input)) => input.to_string(),
+        (None, None) => return Err(Error::NoInput),
+    };
+    let name = source.as_ref().map_or("<built in>", |s| s.name());
+    Ok((input, name.to_string()))
+}
+
+/// The answer to one part of a puzzle and the time it took to solve it.
+pub struct Solution {
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Solves the selected parts of a puzzle without printing the answers. Stops
+/// at the first error, which is then the last result.
+pub fn solve_puzzle(puzzle: &Puzzle, options: &Options) -> Vec<Result<Solution, Error>> {
+    solve_puzzle_with(puzzle, options, &puzzle.part1, &puzzle.part2)
+}
+
+/// Like `solve_puzzle`, but solves the parts with `part1` and `part2` instead
+/// of the puzzle's own solvers, such as to solve them with other settings.
+pub fn solve_puzzle_with(
+    puzzle: &Puzzle,
+    options: &Options,
+    part1: &dyn Fn(&str) -> Result<Answer, ParseError>,
+    part2: &dyn Fn(&str) -> Result<Answer, ParseError>,
+) -> Vec<Result<Solution, Error>> {
+    let (input, name) = match load_input(puzzle, &options.source) {
+        Ok(input) => input,
+        Err(err) => return vec![Err(err)],
+    };
+    let mut results = Vec::new();
+    let parts = [(1, part1), (2, part2)];
+    for (part, solve) in parts.iter() {
+        if options.part.is_some() && options.part != Some(*part) {
+            continue;
+        }
+        let instant = Instant::now();
+        match solve(&input) {
+            Ok(answer) => results.push(Ok(Solution {
+                part: *part,
+                answer,
+                elapsed: instant.elapsed(),
+            })),
+            Err(err) => {
+                results.push(Err(err.in_file(&name).into()));
+                break;
+            }
+        }
+    }
+    results
+}
+
+/// Prints which file is read, in text format.
+pub fn print_input(puzzle: &Puzzle, options: &Options) {
+    if let Some(Source::File(file)) = &options.source {
+        if options.format == Format::Text {
+            println!("Day {}: reading file: {}", puzzle.day, file);
+        }
+    }
+}
+
+/// Prints the answers from `solve_puzzle` up to the first error, which is
+/// returned.
+pub fn print_solutions(
+    puzzle: &Puzzle,
+    options: &Options,
+    results: Vec<Result<Solution, Error>>,
+) -> Result<(), Error> {
+    for result in results {
+        let solution = result?;
+        print_answer(
+            options.format,
+            puzzle.day,
+            solution.part,
+            solution.answer,
+            solution.elapsed,
+        );
+    }
+    Ok(())
+}
+
+/// Runs the selected parts of a puzzle on the input in `options`, or on the
+/// puzzle's built in input if no input is given.
+pub fn run_puzzle(puzzle: &Puzzle, options: &Options) -> Result<(), Error> {
+    set_verbose(options.format == Format::Text);
+    print_input(puzzle, options);
+    print_solutions(puzzle, options, solve_puzzle(puzzle, options))
+}
+
+/// The `main` of the day binaries.
+pub fn main(puzzle: &Puzzle) {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!(
+                "{}\n\nUsage: day{} <input file|-> [options]\n\n{}",
+                err, puzzle.day, OPTIONS_USAGE
+            );
+            process::exit(2);
+        }
+    };
+    if let Err(err) = run_puzzle(puzzle, &options) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+/// Runs an extra command of a day binary, which parses its own options with
+/// `parse`. Exits like `main` on errors.
+pub fn run_command<T>(
+    args: &[String],
+    parse: fn(&[String]) -> Result<T, String>,
+    run: fn(&T) -> Result<(), Error>,
+    usage: &str,
+) {
+    let options = match parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, usage);
+            process::exit(2);
+        }
+    };
+    if let Err(err) = run(&options) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format_option() {
+        let args = ["input.txt", "--format", "json", "--part", "2"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<String>>();
+        let options = parse_options(&args).unwrap();
+        assert_eq!(options.source, Some(Source::File("input.txt".to_string())));
+        assert_eq!(options.part, Some(2));
+        assert_eq!(options.format, Format::Json);
+        assert!(parse_options(&["--format".to_string(), "xml".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_input_sources() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+        let options = parse_options(&args(&["-"])).unwrap();
+        assert_eq!(options.source, Some(Source::Stdin));
+        let options = parse_options(&args(&["--input", "0,3,6"])).unwrap();
+        assert_eq!(options.source, Some(Source::Text("0,3,6".to_string())));
+        assert!(parse_options(&args(&["input.txt", "--input", "0,3,6"])).is_err());
+    }
+
+    #[test]
+    fn json_answer_line() {
+        let elapsed = Duration::from_micros(1500);
+        assert_eq!(
+            json_line(16, 2, Answer::Number(42), elapsed),
+            r#"{"day":16,"part":2,"answer":42,"elapsed_ms":1.500}"#
+        );
+        assert_eq!(
+            json_line(5, 2, Answer::NotFound, elapsed),
+            r#"{"day":5,"part":2,"answer":null,"elapsed_ms":1.500}"#
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+/// An error in the puzzle input. Lines and columns are 1-based and `text` is
+/// the whole offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: &str) -> Self {
+        Self {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates an error pointing at `part`, which must be a slice of `text`.
+    pub fn at(line: usize, text: &str, part: &str, message: &str) -> Self {
+        Self::new(line, column_of(text, part), text, message)
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        writeln!(
+            f,
+            "{}:{}:{}: {}",
+            file, self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Returns the 1-based column where `part` starts in `text`, or 1 if `part`
+/// isn't a slice of `text`.
+pub fn column_of(text: &str, part: &str) -> usize {
+    let start = text.as_ptr() as usize;
+    let pos = part.as_ptr() as usize;
+    if pos >= start && pos <= start + text.len() {
+        text[..pos - start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+/// Parses `part` of the line `text`, reporting its position on failure.
+pub fn parse_field<T: FromStr>(line: usize, text: &str, part: &str) -> Result<T, ParseError> {
+    part.parse::<T>()
+        .map_err(|_| ParseError::at(line, text, part, &format!("invalid value '{}'", part)))
+}
+
+/// An error that stops a solution from running.
+#[derive(Debug)]
+pub enum Error {
+    Io(String, io::Error),
+    Parse(ParseError),
+    NoInput,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(file, err) => write!(f, "{}: {}", file, err),
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::NoInput => write!(f, "No input file specified"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub fn read_file(file: &str) -> Result<String, Error> {
+    fs::read_to_string(file).map_err(|err| Error::Io(file.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_slice() {
+        let text = "acc +x1";
+        assert_eq!(column_of(text, &text[4..]), 5);
+        // Not a literal, which could be merged into the one above
+        let other = "+x1".to_string();
+        assert_eq!(column_of(text, &other), 1);
+    }
+
+    #[test]
+    fn display_points_at_column() {
+        let text = "acc +x1";
+        let err = parse_field::<i32>(2, text, &text[4..])
+            .unwrap_err()
+            .in_file("a.txt");
+        assert_eq!(
+            err.to_string(),
+            "a.txt:2:5: invalid value '+x1'\n    acc +x1\n        ^"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+pub mod cli;
+pub mod error;
+pub mod par;
+pub mod rng;
+pub mod testing;
+
+pub use error::{parse_field, read_file, Error, ParseError};
+pub use rng::Rng;
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Solves one part of a puzzle for the given input text.
+pub type Solver = fn(&str) -> Result<Answer, ParseError>;
+
+/// Parses a puzzle input, returning the number of records read.
+pub type Parser = fn(&str) -> Result<usize, ParseError>;
+
+/// A generated puzzle input, with the answers that are known from how it was
+/// generated.
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Generated {
+    pub fn new(input: String) -> Self {
+        Self {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+/// Generates random inputs for a day. What the size means depends on the day,
+/// but it's roughly the number of records.
+pub struct Generator {
+    pub default_size: usize,
+    /// The largest size the generator can make, if it's limited.
+    pub max_size: Option<usize>,
+    pub generate: fn(&mut Rng, usize) -> Generated,
+}
+
+/// Describes a day's puzzle for the day binaries and the `aoc` runner.
+pub struct Puzzle {
+    pub day: u32,
+    /// Input used when no input file is given.
+    pub builtin_input: Option<&'static str>,
+    pub parse: Parser,
+    pub part1: Solver,
+    pub part2: Solver,
+    pub generator: Generator,
+}
+
+static VERBOSE: AtomicBool = AtomicBool::new(true);
+
+/// Turns the debug output printed with `verbose!` on or off.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Like `println!`, but only prints when verbose output is on.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::is_verbose() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    NotFound,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::NotFound => write!(f, "not found"),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i64)
+    }
+}
@@ -0,0 +1,46 @@
+//! Runs independent work on a thread pool when the `parallel` feature is
+//! enabled, and serially otherwise. Both give the same results in the same
+//! order.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Maps `f` over `items`, keeping their order.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).collect();
+}
+
+/// Returns the result of `f` for the first item where it isn't `None`. When
+/// run in parallel the search stops early once that item is known.
+pub fn find_map_first<T, R, F>(items: &[T], f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().find_map_first(f);
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().find_map(f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_in_order() {
+        let items = (0..1000).collect::<Vec<u32>>();
+        assert_eq!(map(&items, |n| n * 2)[999], 1998);
+        let first = find_map_first(&items, |n| if n % 7 == 6 { Some(*n) } else { None });
+        assert_eq!(first, Some(6));
+    }
+}
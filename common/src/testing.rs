@@ -0,0 +1,38 @@
+use crate::{Answer, ParseError};
+
+/// The known answers for one puzzle input. A part is not checked when its
+/// answer is `None`, e.g. when an example only covers one of the parts.
+pub struct Expected {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Runs the solvers on every input and panics with a list of all answers that
+/// differ from the expected ones.
+pub fn check_answers(
+    expected: &[Expected],
+    solve_part1: fn(&str) -> Result<Answer, ParseError>,
+    solve_part2: fn(&str) -> Result<Answer, ParseError>,
+) {
+    let mut failures = Vec::new();
+    for e in expected.iter() {
+        let parts = [(1, e.part1, solve_part1), (2, e.part2, solve_part2)];
+        for (part, answer, solve) in parts.iter() {
+            if let Some(answer) = answer {
+                match solve(e.input) {
+                    Ok(actual) if actual == *answer => {}
+                    Ok(actual) => failures.push(format!(
+                        "{} part {}: expected {}, got {}",
+                        e.name, part, answer, actual
+                    )),
+                    Err(err) => failures.push(format!("{} part {}: {}", e.name, part, err)),
+                }
+            }
+        }
+    }
+    if !failures.is_empty() {
+        panic!("Wrong answers:\n{}", failures.join("\n"));
+    }
+}
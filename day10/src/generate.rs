@@ -0,0 +1,28 @@
+use common::{Generated, Rng};
+
+// Number of arrangements of a run of 1-jolt differences of each length
+const RUN_ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+
+/// Generates `size` adapters in random order, with runs of one to four 1-jolt
+/// differences between 3-jolt differences. Once the number of arrangements
+/// gets close to overflowing the runs are kept at one.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut adapters = Vec::new();
+    let mut joltage = 0;
+    let mut arrangements = 1u64;
+    while adapters.len() < size.max(1) {
+        let mut run = rng.range(1, 4) as usize;
+        if arrangements > i64::MAX as u64 / RUN_ARRANGEMENTS[run] {
+            run = 1;
+        }
+        arrangements *= RUN_ARRANGEMENTS[run];
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        joltage += 2;
+    }
+    adapters.truncate(size.max(1));
+    rng.shuffle(&mut adapters);
+    Generated::new(adapters.iter().map(|a| format!("{}\n", a)).collect())
+}
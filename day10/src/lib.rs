@@ -0,0 +1,72 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+
+mod generate;
+
+fn read_joltage_adapters(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| parse_field(idx + 1, l, l))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_joltage_adapters(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let mut adapters = read_joltage_adapters(input)?;
+
+    adapters.sort();
+    adapters.insert(0, 0);
+    adapters.push(adapters.last().unwrap() + 3);
+
+    let mut diffs1 = 0;
+    let mut diffs3 = 0;
+    let mut last_adapter = 0;
+    for a in adapters.iter() {
+        let diff = *a - last_adapter;
+        last_adapter = *a;
+        if diff == 1 {
+            diffs1 += 1;
+        }
+        if diff == 3 {
+            diffs3 += 1;
+        }
+    }
+    Ok((diffs1 * diffs3).into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let mut adapters = read_joltage_adapters(input)?;
+    adapters.push(0);
+    adapters.sort();
+    adapters.push(adapters.last().unwrap() + 3);
+
+    let mut arrangements = vec![1];
+    for i in 1..adapters.len() {
+        let mut sum = 0u64;
+        for j in (0..i).rev() {
+            if adapters[i] - adapters[j] > 3 {
+                break;
+            }
+            sum += arrangements[j];
+        }
+        arrangements.push(sum);
+    }
+
+    Ok((*arrangements.last().unwrap()).into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 10,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 90,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
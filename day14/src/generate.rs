@@ -0,0 +1,22 @@
+use common::{Generated, Rng};
+
+/// Generates a program of about `size` lines, with a new mask every one to six
+/// writes. Masks have at most 9 floating bits, like the real input.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut lines = Vec::new();
+    while lines.len() < size.max(2) {
+        let mut mask = (0..36)
+            .map(|_| if rng.chance(0.5) { '0' } else { '1' })
+            .collect::<Vec<char>>();
+        for _ in 0..rng.range(0, 9) {
+            mask[rng.index(36)] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+        for _ in 0..rng.range(1, 6) {
+            let address = rng.range(0, 65535);
+            let value = rng.range(0, 1 << 30);
+            lines.push(format!("mem[{}] = {}", address, value));
+        }
+    }
+    Generated::new(lines.join("\n") + "\n")
+}
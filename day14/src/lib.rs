@@ -0,0 +1,159 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+mod generate;
+
+lazy_static! {
+    static ref MEM_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$",).unwrap();
+    static ref MASK_RE: Regex = Regex::new(r"^mask = (.+)$",).unwrap();
+}
+
+enum Command {
+    Mem(u64, u64),  // address, value
+    Mask(u64, u64), // mem write mask, value
+}
+
+fn parse_line(line_no: usize, line: &str) -> Result<Command, ParseError> {
+    if line.starts_with("mem") {
+        if let Some(caps) = MEM_RE.captures(line) {
+            Ok(Command::Mem(
+                parse_field(line_no, line, &caps[1])?,
+                parse_field(line_no, line, &caps[2])?,
+            ))
+        } else {
+            Err(ParseError::new(
+                line_no,
+                1,
+                line,
+                "expected 'mem[<address>] = <value>'",
+            ))
+        }
+    } else if line.starts_with("mask") {
+        if let Some(caps) = MASK_RE.captures(line) {
+            let bits = caps.get(1).unwrap();
+            if let Some((pos, c)) = bits
+                .as_str()
+                .char_indices()
+                .find(|(_, c)| !"X01".contains(*c))
+            {
+                let message = format!("unexpected mask bit '{}'", c);
+                return Err(ParseError::new(
+                    line_no,
+                    bits.start() + pos + 1,
+                    line,
+                    &message,
+                ));
+            }
+            if bits.as_str().len() != 36 {
+                let message = format!("expected 36 mask bits, found {}", bits.as_str().len());
+                return Err(ParseError::new(line_no, bits.start() + 1, line, &message));
+            }
+            let mask = caps[1]
+                .chars()
+                .fold(0, |mask, c| (mask << 1) | (if c == 'X' { 1 } else { 0 }));
+            let value = caps[1]
+                .chars()
+                .fold(0, |mask, c| (mask << 1) | (if c == '1' { 1 } else { 0 }));
+            Ok(Command::Mask(mask, value))
+        } else {
+            Err(ParseError::new(
+                line_no,
+                1,
+                line,
+                "expected 'mask = <bits>'",
+            ))
+        }
+    } else {
+        Err(ParseError::new(
+            line_no,
+            1,
+            line,
+            "expected 'mem' or 'mask'",
+        ))
+    }
+}
+
+fn read_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_commands(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let cmds = read_commands(input)?;
+    let mut memory = HashMap::new();
+    let mut write_mask: u64 = 0;
+    let mut mask_value: u64 = 0;
+    for cmd in cmds.iter() {
+        match cmd {
+            Command::Mask(mask, value) => {
+                write_mask = *mask;
+                mask_value = *value;
+            }
+            Command::Mem(address, value) => {
+                let new_value = (value & write_mask) | mask_value;
+                memory.insert(address, new_value);
+            }
+        }
+    }
+    let sum = memory.values().sum::<u64>();
+    Ok(sum.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let cmds = read_commands(input)?;
+    let mut memory = HashMap::<u64, u64>::new();
+    let mut mask_floating: u64 = 0;
+    let mut one_mask: u64 = 0;
+    for cmd in cmds.iter() {
+        match cmd {
+            Command::Mask(mask, value) => {
+                mask_floating = *mask;
+                one_mask = *value;
+            }
+            Command::Mem(address, value) => {
+                let num_variations = 2u64.pow(mask_floating.count_ones());
+                // Calculate the floating address variations by distributing the
+                // counter bits where the address mask is 'X'
+                for counter in 0..num_variations {
+                    let mut counter_bit_mask = 1;
+                    let mut floating_bits = 0;
+                    for n in (0..36).rev() {
+                        floating_bits <<= 1;
+                        if mask_floating & (1 << n) != 0 {
+                            if counter & counter_bit_mask != 0 {
+                                floating_bits |= 1;
+                            }
+                            counter_bit_mask <<= 1;
+                        }
+                    }
+                    let new_address = ((address | one_mask) & !mask_floating) | floating_bits;
+                    memory.insert(new_address, *value);
+                }
+            }
+        }
+    }
+    let sum = memory.values().sum::<u64>();
+    Ok(sum.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 14,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 580,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
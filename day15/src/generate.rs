@@ -0,0 +1,13 @@
+use common::{Generated, Rng};
+
+/// Generates `size` different starting numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(1);
+    let mut numbers = (0..size as u64 * 3).collect::<Vec<u64>>();
+    rng.shuffle(&mut numbers);
+    let numbers = numbers[..size]
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>();
+    Generated::new(numbers.join(",") + "\n")
+}
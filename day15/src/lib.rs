@@ -0,0 +1,94 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+use std::collections::HashMap;
+
+mod generate;
+
+/*
+Part 2 solved with play_map and play_vec:
+
+$ cargo run
+Part1: 260
+Part2 (map): 950 (15469ms)
+Part2 (vec): 950 (2192ms)
+
+$ cargo run --release
+Part1: 260
+Part2 (map): 950 (1456ms)
+Part2 (vec): 950 (416ms)
+*/
+
+pub const INPUT: &str = "13,0,10,12,1,5,8";
+
+fn parse_numbers(input: &str) -> Result<Vec<u32>, ParseError> {
+    let line = input.trim_end();
+    line.split(',').map(|n| parse_field(1, line, n)).collect()
+}
+
+fn play_map(numbers: &[u32], turns: u32) -> u32 {
+    let mut previous_turns = numbers
+        .iter()
+        .enumerate()
+        .map(|(idx, n)| (*n, (idx + 1) as u32))
+        .collect::<HashMap<u32, u32>>();
+    let mut last_number = *numbers.last().unwrap();
+    for last_turn in (numbers.len() as u32)..turns {
+        let previous_turn = previous_turns.insert(last_number, last_turn);
+        last_number = if let Some(previous_turn) = previous_turn {
+            last_turn - previous_turn
+        } else {
+            0
+        };
+    }
+    last_number
+}
+
+fn play_vec(numbers: &[u32], turns: u32) -> u32 {
+    // Later numbers are all below `turns`, so starting numbers that aren't
+    // are never said again and are left out of the table
+    let mut previous_turns = vec![0; turns as usize];
+    for (idx, n) in numbers.iter().enumerate() {
+        if let Some(turn) = previous_turns.get_mut(*n as usize) {
+            *turn = (idx + 1) as u32;
+        }
+    }
+    let mut last_number = *numbers.last().unwrap();
+    for last_turn in (numbers.len() as u32)..turns {
+        let previous_turn = match previous_turns.get_mut(last_number as usize) {
+            Some(turn) => std::mem::replace(turn, last_turn),
+            None => 0,
+        };
+        last_number = if previous_turn != 0 {
+            last_turn - previous_turn
+        } else {
+            0
+        };
+    }
+    last_number
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_numbers(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let numbers = parse_numbers(input)?;
+    Ok(play_map(&numbers, 2020).into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let numbers = parse_numbers(input)?;
+    Ok(play_vec(&numbers, 30000000).into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 15,
+    parse,
+    builtin_input: Some(INPUT),
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 7,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,35 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day15::{solve_part1, solve_part2, INPUT};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "0,3,6",
+        input: "0,3,6",
+        part1: Some(Number(436)),
+        part2: Some(Number(175594)),
+    },
+    Expected {
+        name: "1,3,2",
+        input: "1,3,2",
+        part1: Some(Number(1)),
+        part2: None,
+    },
+    Expected {
+        name: "3,1,2",
+        input: "3,1,2",
+        part1: Some(Number(1836)),
+        part2: None,
+    },
+    Expected {
+        name: "INPUT",
+        input: INPUT,
+        part1: Some(Number(260)),
+        part2: Some(Number(950)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
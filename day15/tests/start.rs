@@ -0,0 +1,15 @@
+use common::Answer::Number;
+use day15::{solve_part1, solve_part2};
+
+#[test]
+fn starting_numbers_above_the_turns() {
+    assert_eq!(solve_part2("40000000").unwrap(), Number(286293));
+    // Far too large to size a table for
+    assert_eq!(solve_part2("4000000000").unwrap(), Number(286293));
+    // Numbers this large are never said again, so which one it is doesn't
+    // matter
+    assert_eq!(
+        solve_part1("3,40000000").unwrap(),
+        solve_part1("3,50000000").unwrap()
+    );
+}
@@ -0,0 +1,130 @@
+use common::{Answer, Generated, Rng};
+
+const FIELDS: &[&str] = &[
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+// The values allowed by the rule on each level. Every level allows all the
+// values of the levels below it, so the field on level n is valid for the
+// rules on level n and up, which makes the fields possible to identify.
+fn ranges(level: u64) -> ((u64, u64), (u64, u64)) {
+    (
+        (30 - level, 100 + 20 * level),
+        (900 - 20 * level, 970 + level),
+    )
+}
+
+fn valid_value(rng: &mut Rng, level: u64) -> u64 {
+    let (low, high) = ranges(level);
+    if rng.chance(0.5) {
+        rng.range(low.0, low.1)
+    } else {
+        rng.range(high.0, high.1)
+    }
+}
+
+// A value that is valid for the rule on `level` but not for the one below
+fn new_value(rng: &mut Rng, level: u64) -> u64 {
+    if level == 0 {
+        valid_value(rng, 0)
+    } else {
+        let (previous, _) = ranges(level - 1);
+        let (current, _) = ranges(level);
+        rng.range(previous.1 + 1, current.1)
+    }
+}
+
+fn invalid_value(rng: &mut Rng) -> u64 {
+    let top = FIELDS.len() as u64 - 1;
+    let (low, high) = ranges(top);
+    match rng.range(0, 2) {
+        0 => rng.range(0, low.0 - 1),
+        1 => rng.range(low.1 + 1, high.0 - 1),
+        _ => rng.range(high.1 + 1, 999),
+    }
+}
+
+fn ticket(values: &[u64]) -> String {
+    let values = values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>();
+    values.join(",") + "\n"
+}
+
+/// Generates the 20 rules from the real input, your ticket and `size` nearby
+/// tickets, where about a fifth of the nearby tickets have an invalid value.
+/// Both answers are known.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let num_fields = FIELDS.len();
+    // The level of each rule, and the rule of each ticket position
+    let mut levels = (0..num_fields as u64).collect::<Vec<u64>>();
+    rng.shuffle(&mut levels);
+    let mut positions = (0..num_fields).collect::<Vec<usize>>();
+    rng.shuffle(&mut positions);
+
+    let mut input = String::new();
+    for (name, level) in FIELDS.iter().zip(levels.iter()) {
+        let (low, high) = ranges(*level);
+        input += &format!("{}: {}-{} or {}-{}\n", name, low.0, low.1, high.0, high.1);
+    }
+    let your_ticket = positions
+        .iter()
+        .map(|rule| valid_value(rng, levels[*rule]))
+        .collect::<Vec<u64>>();
+    input += &format!(
+        "\nyour ticket:\n{}\nnearby tickets:\n",
+        ticket(&your_ticket)
+    );
+
+    let mut error_rate = 0;
+    for idx in 0..size.max(1) {
+        let mut values = positions
+            .iter()
+            .map(|rule| {
+                if idx == 0 {
+                    new_value(rng, levels[*rule])
+                } else {
+                    valid_value(rng, levels[*rule])
+                }
+            })
+            .collect::<Vec<u64>>();
+        if idx > 0 && rng.chance(0.2) {
+            let invalid = invalid_value(rng);
+            values[rng.index(num_fields)] = invalid;
+            error_rate += invalid;
+        }
+        input += &ticket(&values);
+    }
+
+    let departure = positions
+        .iter()
+        .zip(your_ticket.iter())
+        .filter(|(rule, _)| FIELDS[**rule].starts_with("departure"))
+        .map(|(_, value)| value)
+        .product::<u64>();
+    Generated {
+        input,
+        part1: Some(Answer::from(error_rate)),
+        part2: Some(Answer::from(departure)),
+    }
+}
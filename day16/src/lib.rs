@@ -0,0 +1,316 @@
+use common::{parse_field, verbose, Answer, Generator, ParseError, Puzzle};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+mod generate;
+
+lazy_static! {
+    static ref RULE_RE: Regex = Regex::new(
+        r"(?x)
+            ^
+            (?P<name>\D+)
+            :\s
+            (?P<start0>\d+)
+            -
+            (?P<end0>\d+)
+            \sor\s
+            (?P<start1>\d+)
+            -
+            (?P<end1>\d+)
+            $
+        ",
+    )
+    .unwrap();
+}
+
+// const TICKET_SIZE: usize = 3; // example.txt
+
+// const TICKET_SIZE: usize = 3; // example.txt
+
+type Ticket = Vec<u32>;
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    ranges: Vec<(u32, u32)>,
+}
+
+impl Rule {
+    fn new(name: &str, ranges: Vec<(u32, u32)>) -> Self {
+        Self {
+            name: name.to_string(),
+            ranges,
+        }
+    }
+
+    fn is_num_valid(&self, ticket_num: u32) -> bool {
+        for r in self.ranges.iter() {
+            if ticket_num >= r.0 && ticket_num <= r.1 {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn get_ticket_error_rate(rules: &[Rule], ticket: &Ticket) -> u32 {
+    let mut all_status = vec![false; ticket.len()];
+    for (i, ticket_num) in ticket.iter().enumerate() {
+        for rule in rules.iter() {
+            if rule.is_num_valid(*ticket_num) {
+                all_status[i] = true;
+                break;
+            }
+        }
+    }
+    let mut error_rate = 0;
+    for (status, ticket_num) in all_status.iter().zip(ticket.iter()) {
+        if !status {
+            error_rate += ticket_num;
+        }
+    }
+    error_rate
+}
+
+fn is_ticket_valid(rules: &[Rule], ticket: &Ticket) -> bool {
+    ticket
+        .iter()
+        .all(|ticket_num| rules.iter().any(|rule| rule.is_num_valid(*ticket_num)))
+}
+
+fn parse_ticket(line_no: usize, line: &str, num_fields: usize) -> Result<Ticket, ParseError> {
+    let ticket = line
+        .split(',')
+        .map(|n| parse_field(line_no, line, n))
+        .collect::<Result<Ticket, ParseError>>()?;
+    if ticket.len() != num_fields {
+        let message = format!("expected {} fields, found {}", num_fields, ticket.len());
+        return Err(ParseError::new(line_no, 1, line, &message));
+    }
+    Ok(ticket)
+}
+
+fn read_lines(input: &str) -> Vec<String> {
+    input.lines().map(|line| line.to_string()).collect()
+}
+
+fn parse_rule(line_no: usize, line: &str) -> Result<Rule, ParseError> {
+    let caps = RULE_RE.captures(line);
+    if let Some(caps) = caps {
+        let field = |name| parse_field::<u32>(line_no, line, &caps[name]);
+        Ok(Rule::new(
+            &caps["name"],
+            vec![
+                (field("start0")?, field("end0")?),
+                (field("start1")?, field("end1")?),
+            ],
+        ))
+    } else {
+        let message = "expected '<name>: <start>-<end> or <start>-<end>'";
+        Err(ParseError::new(line_no, 1, line, message))
+    }
+}
+
+// Returns the index of the line after the section header
+fn find_section(lines: &[String], header: &str) -> Result<usize, ParseError> {
+    if let Some(idx) = lines.iter().position(|line| line == header) {
+        Ok(idx + 1)
+    } else {
+        let message = format!("missing '{}' section", header);
+        Err(ParseError::new(lines.len() + 1, 1, "", &message))
+    }
+}
+
+fn parse_nearby_tickets(lines: &[String], num_fields: usize) -> Result<Vec<Ticket>, ParseError> {
+    let start = find_section(lines, "nearby tickets:")?;
+    lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| parse_ticket(idx + 1, line, num_fields))
+        .collect()
+}
+
+fn parse_your_ticket(lines: &[String], num_fields: usize) -> Result<Ticket, ParseError> {
+    let idx = find_section(lines, "your ticket:")?;
+    let line = lines.get(idx).map(|line| line.as_str()).unwrap_or("");
+    parse_ticket(idx + 1, line, num_fields)
+}
+
+fn parse_rules(lines: &[String]) -> Result<Vec<Rule>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .take_while(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| parse_rule(idx + 1, line))
+        .collect()
+}
+
+fn get_valid_rules(tickets: &[Ticket], rules: &[Rule], num_index: usize) -> Vec<String> {
+    let mut valid_rules = Vec::new();
+    for rule in rules.iter() {
+        let mut rule_is_valid = true;
+        for ticket in tickets.iter() {
+            let ticket_num = ticket[num_index];
+            if !rule.is_num_valid(ticket_num) {
+                rule_is_valid = false;
+                break;
+            }
+        }
+        if rule_is_valid {
+            valid_rules.push(rule.name.clone());
+        }
+    }
+    valid_rules
+}
+
+fn assign_rules(valid_rules_per_field: &[Vec<String>]) -> Vec<String> {
+    let mut removed_rules = HashSet::new();
+    let mut assigned_rules = HashMap::new();
+    verbose!("{:?}", valid_rules_per_field);
+    loop {
+        // Find field with least valid rules
+        let mut best_idx = 0;
+        for (idx, rules) in valid_rules_per_field.iter().enumerate() {
+            let best = valid_rules_per_field[best_idx].iter().fold(0, |acc, rule| {
+                acc + if removed_rules.contains(rule) { 0 } else { 1 }
+            });
+            let this = rules.iter().fold(0, |acc, rule| {
+                acc + if removed_rules.contains(rule) { 0 } else { 1 }
+            });
+            if (this < best || best == 0) && this != 0 {
+                best_idx = idx;
+            }
+        }
+        let valid_rules = &valid_rules_per_field[best_idx];
+
+        let best = valid_rules_per_field[best_idx].iter().fold(0, |acc, rule| {
+            acc + if removed_rules.contains(rule) { 0 } else { 1 }
+        });
+        verbose!("best {}", best);
+
+        // Find rules that hasn't been removed yet
+        let mut potential_valid = Vec::new();
+        for valid_rule in valid_rules.iter() {
+            if !removed_rules.contains(valid_rule) {
+                potential_valid.push(valid_rule);
+            }
+        }
+
+        // Choose the rule with least occurances in other fields
+        let valid_rule = potential_valid.iter().fold(("", i32::MAX), |acc, rule| {
+            let occurances = valid_rules_per_field.iter().fold(0, |acc, rules| {
+                acc + if rules.contains(rule) { 1 } else { 0 }
+            });
+            if occurances < acc.1 {
+                (rule, occurances)
+            } else {
+                acc
+            }
+        });
+
+        if valid_rule.1 == i32::MAX {
+            break;
+        }
+
+        removed_rules.insert(valid_rule.0.to_string());
+        assigned_rules.insert(best_idx, valid_rule.0.to_string());
+    }
+    let mut rules = vec!["<N/A>".to_string(); valid_rules_per_field.len()];
+    for (key, rule) in assigned_rules.iter() {
+        rules[*key] = rule.clone();
+    }
+    rules
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    let lines = read_lines(input);
+    let rules = parse_rules(&lines)?;
+    parse_your_ticket(&lines, rules.len())?;
+    Ok(parse_nearby_tickets(&lines, rules.len())?.len() + 1)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let lines = read_lines(input);
+    let rules = parse_rules(&lines)?;
+    let nearby_tickets = parse_nearby_tickets(&lines, rules.len())?;
+    let mut error_rate = 0;
+    for ticket in nearby_tickets.iter() {
+        error_rate += get_ticket_error_rate(&rules, ticket);
+    }
+    Ok(error_rate.into())
+}
+
+// The error rate can't be used to discard tickets, since a ticket with an
+// invalid 0 has an error rate of 0.
+fn identify_fields(rules: &[Rule], nearby_tickets: &[Ticket]) -> Vec<String> {
+    let nearby_valid = nearby_tickets
+        .iter()
+        .filter(|t| is_ticket_valid(rules, t))
+        .cloned()
+        .collect::<Vec<Ticket>>();
+
+    let mut valid_rules = Vec::new();
+    for i in 0..rules.len() {
+        let valid_for_field = get_valid_rules(&nearby_valid, rules, i);
+        valid_rules.push(valid_for_field);
+    }
+    assign_rules(&valid_rules)
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let lines = read_lines(input);
+    let rules = parse_rules(&lines)?;
+    let your_ticket = parse_your_ticket(&lines, rules.len())?;
+    let nearby_tickets = parse_nearby_tickets(&lines, rules.len())?;
+    let assigned_rules = identify_fields(&rules, &nearby_tickets);
+
+    verbose!("Your ticket:");
+    let mut answer = 1;
+    for (name, number) in assigned_rules.iter().zip(your_ticket.iter()) {
+        verbose!("{}: {}", name, number);
+        if name.starts_with("departure") {
+            answer *= (*number) as u64;
+        }
+    }
+    Ok(answer.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 16,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 240,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identify_input_fields(input: &str) -> Vec<String> {
+        let lines = read_lines(input);
+        let rules = parse_rules(&lines).unwrap();
+        let nearby_tickets = parse_nearby_tickets(&lines, rules.len()).unwrap();
+        identify_fields(&rules, &nearby_tickets)
+    }
+
+    #[test]
+    fn identify_example_fields() {
+        let fields = identify_input_fields(include_str!("../example2.txt"));
+        assert_eq!(fields, vec!["row", "class", "seat"]);
+    }
+
+    #[test]
+    fn identify_all_input_fields() {
+        let fields = identify_input_fields(include_str!("../input.txt"));
+        assert!(!fields.contains(&"<N/A>".to_string()), "{:?}", fields);
+    }
+}
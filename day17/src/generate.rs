@@ -0,0 +1,14 @@
+use common::{Generated, Rng};
+
+/// Generates a `size` x `size` starting slice with about half the cubes
+/// active.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        for _ in 0..size.max(1) {
+            input.push(if rng.chance(0.5) { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    Generated::new(input)
+}
@@ -0,0 +1,137 @@
+use common::{verbose, Answer, Generator, ParseError, Puzzle};
+use grid::neighbor_offsets;
+use std::collections::HashSet;
+
+mod generate;
+
+type Cube = [i64; 4];
+
+struct Pocket {
+    map: HashSet<Cube>,
+    neighbors: Vec<Cube>,
+}
+
+impl Pocket {
+    fn new() -> Self {
+        Self {
+            map: HashSet::new(),
+            neighbors: neighbor_offsets::<4>(),
+        }
+    }
+
+    fn activate(&mut self, cube: Cube) {
+        self.map.insert(cube);
+    }
+
+    fn get_status(&self, cube: &Cube) -> bool {
+        self.map.contains(cube)
+    }
+
+    fn get_num_active(&self) -> usize {
+        self.map.len()
+    }
+
+    fn get_range(&self) -> (Cube, Cube) {
+        let mut active = self.map.iter();
+        let first = *active.next().expect("No active cells");
+        let (mut min, mut max) = (first, first);
+        for a in active {
+            for dim in 0..4 {
+                min[dim] = min[dim].min(a[dim]);
+                max[dim] = max[dim].max(a[dim]);
+            }
+        }
+        (min.map(|v| v - 1), max.map(|v| v + 1))
+    }
+
+    fn calculate_cube(&self, cube: &Cube) -> bool {
+        let active_count = self
+            .neighbors
+            .iter()
+            .filter(|offset| {
+                let mut neighbor = *cube;
+                for dim in 0..4 {
+                    neighbor[dim] += offset[dim];
+                }
+                self.get_status(&neighbor)
+            })
+            .count();
+        /*
+        If a cube is active and exactly 2 or 3 of its neighbors are also active, the cube remains active. Otherwise, the cube becomes inactive.
+        If a cube is inactive but exactly 3 of its neighbors are active, the cube becomes active. Otherwise, the cube remains inactive.
+        */
+        let status = self.get_status(cube);
+        if status && (active_count == 2 || active_count == 3) {
+            true
+        } else {
+            !status && active_count == 3
+        }
+    }
+
+    fn step(&mut self, simulate_w: bool) {
+        if self.map.is_empty() {
+            return;
+        }
+        let mut new_map = HashSet::new();
+        let (min, max) = self.get_range();
+        let (minw, maxw) = if simulate_w { (min[3], max[3]) } else { (0, 0) };
+        let mut count = 0;
+        for w in minw..maxw + 1 {
+            for z in min[2]..max[2] + 1 {
+                for y in min[1]..max[1] + 1 {
+                    for x in min[0]..max[0] + 1 {
+                        count += 1;
+                        if self.calculate_cube(&[x, y, z, w]) {
+                            new_map.insert([x, y, z, w]);
+                        }
+                    }
+                }
+            }
+        }
+        verbose!("Cube count: {}", count);
+        self.map = new_map;
+    }
+}
+
+fn load_pocket(input: &str) -> Result<Pocket, ParseError> {
+    let mut pocket = Pocket::new();
+    for ((x, y), c) in grid::parse(input)?.iter() {
+        if *c == '#' {
+            pocket.activate([x, y, 0, 0]);
+        }
+    }
+    Ok(pocket)
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(load_pocket(input)?.map.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let mut pocket = load_pocket(input)?;
+    for _ in 0..6 {
+        pocket.step(false);
+    }
+    Ok(pocket.get_num_active().into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let mut pocket = load_pocket(input)?;
+    for _ in 0..6 {
+        pocket.step(true);
+    }
+    Ok(pocket.get_num_active().into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 17,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 8,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
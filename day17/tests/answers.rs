@@ -0,0 +1,23 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day17::{solve_part1, solve_part2};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "example.txt",
+        input: include_str!("../example.txt"),
+        part1: Some(Number(112)),
+        part2: Some(Number(848)),
+    },
+    Expected {
+        name: "input.txt",
+        input: include_str!("../input.txt"),
+        part1: Some(Number(388)),
+        part2: Some(Number(2280)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
@@ -0,0 +1,35 @@
+use common::{Generated, Rng};
+
+// At most this many digits per line keeps the sum of a million lines within an
+// i64 with any precedence, since no line can be larger than 10^12
+const MAX_DIGITS: u64 = 12;
+
+fn expression(rng: &mut Rng, digits: &mut u64, depth: u32) -> String {
+    let mut text = String::new();
+    for term in 0..rng.range(2, 5) {
+        if *digits == 0 {
+            break;
+        }
+        if term > 0 {
+            text += if rng.chance(0.5) { " + " } else { " * " };
+        }
+        if depth < 2 && *digits >= 2 && rng.chance(0.3) {
+            text += &format!("({})", expression(rng, digits, depth + 1));
+        } else {
+            text += &rng.range(1, 9).to_string();
+            *digits -= 1;
+        }
+    }
+    text
+}
+
+/// Generates `size` expressions with up to two levels of parentheses.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let mut digits = MAX_DIGITS;
+        input += &expression(rng, &mut digits, 0);
+        input.push('\n');
+    }
+    Generated::new(input)
+}
@@ -0,0 +1,144 @@
+use common::{Answer, Generator, ParseError, Puzzle};
+
+mod generate;
+
+type Precedence = fn(char) -> Option<(u32, u32)>;
+
+struct Expression<'a> {
+    line_no: usize,
+    text: &'a str,
+    tokens: Vec<(usize, char)>, // column, token
+}
+
+impl<'a> Expression<'a> {
+    fn new(line_no: usize, text: &'a str) -> Self {
+        let tokens = text
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(col, c)| (col + 1, c))
+            .collect();
+        Self {
+            line_no,
+            text,
+            tokens,
+        }
+    }
+
+    fn token(&self, pos: usize) -> Option<char> {
+        self.tokens.get(pos).map(|t| t.1)
+    }
+
+    fn error(&self, pos: usize, message: &str) -> ParseError {
+        let column = match self.tokens.get(pos) {
+            Some(t) => t.0,
+            None => self.text.chars().count() + 1,
+        };
+        ParseError::new(self.line_no, column, self.text, message)
+    }
+}
+
+// Reference: https://matklad.github.io/2020/04/13/simple-but-powerful-pratt-parsing.html
+fn eval_expression(
+    expr: &Expression,
+    pos: &mut usize,
+    min_bp: u32,
+    precedence: Precedence,
+) -> Result<u64, ParseError> {
+    let c = match expr.token(*pos) {
+        Some(c) => c,
+        None => return Err(expr.error(*pos, "unexpected end of expression")),
+    };
+    *pos += 1;
+    let mut ans = match c {
+        '(' => {
+            let lhs = eval_expression(expr, pos, 0, precedence)?;
+            if expr.token(*pos) != Some(')') {
+                return Err(expr.error(*pos, "expected ')'"));
+            }
+            *pos += 1; // skip )
+            lhs
+        }
+        '0'..='9' => c.to_digit(10).unwrap() as u64,
+        _ => return Err(expr.error(*pos - 1, &format!("unexpected token '{}'", c))),
+    };
+    loop {
+        let op = match expr.token(*pos) {
+            None | Some(')') => break,
+            Some(op) => op,
+        };
+        let (lhs_bp, rhs_bp) = match precedence(op) {
+            Some(bp) => bp,
+            None => return Err(expr.error(*pos, &format!("expected operator, got '{}'", op))),
+        };
+        if lhs_bp < min_bp {
+            break;
+        }
+        *pos += 1;
+        let rhs = eval_expression(expr, pos, rhs_bp, precedence)?;
+        ans = match op {
+            '+' => ans + rhs,
+            '*' => ans * rhs,
+            _ => return Err(expr.error(*pos, &format!("invalid operator '{}'", op))),
+        }
+    }
+    Ok(ans)
+}
+
+fn eval_line(line_no: usize, line: &str, precedence: Precedence) -> Result<u64, ParseError> {
+    let expr = Expression::new(line_no, line);
+    let mut pos = 0;
+    let ans = eval_expression(&expr, &mut pos, 0, precedence)?;
+    if pos < expr.tokens.len() {
+        return Err(expr.error(pos, "unmatched ')'"));
+    }
+    Ok(ans)
+}
+
+fn eval_file(input: &str, precedence: Precedence) -> Result<u64, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| eval_line(idx + 1, l, precedence))
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    let expressions = input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| Expression::new(idx + 1, l))
+        .collect::<Vec<Expression>>();
+    Ok(expressions.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let sum = eval_file(input, |c| match c {
+        '*' => Some((1, 2)),
+        '+' => Some((1, 2)),
+        _ => None,
+    })?;
+    Ok(sum.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let sum = eval_file(input, |c| match c {
+        '*' => Some((1, 2)),
+        '+' => Some((3, 4)),
+        _ => None,
+    })?;
+    Ok(sum.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 18,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 370,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,23 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day18::{solve_part1, solve_part2};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "example.txt",
+        input: include_str!("../example.txt"),
+        part1: Some(Number(26335)),
+        part2: Some(Number(693891)),
+    },
+    Expected {
+        name: "input.txt",
+        input: include_str!("../input.txt"),
+        part1: Some(Number(12956356593940)),
+        part2: Some(Number(94240043727614)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
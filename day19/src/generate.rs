@@ -0,0 +1,97 @@
+use common::{Generated, Rng};
+
+// Rule numbers used by the puzzle itself
+const RESERVED: &[usize] = &[0, 8, 11, 31, 42];
+
+// A rule is a list of alternatives, each a sequence of rule indices into the
+// list of rules, or a terminal
+enum Rule {
+    Terminal(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+fn sample(rng: &mut Rng, rules: &[Rule], rule: usize, message: &mut String) {
+    match &rules[rule] {
+        Rule::Terminal(c) => message.push(*c),
+        Rule::Alternatives(alternatives) => {
+            for part in rng.choose(alternatives).iter() {
+                sample(rng, rules, *part, message);
+            }
+        }
+    }
+}
+
+/// Generates rules in the shape of the real input and `size` messages. Rules
+/// 42 and 31 are built from two levels of rules below them, and every rule
+/// only matches messages of one length, which the solution depends on. Some
+/// messages are made from rule 0, some from the looping rules of part 2 and
+/// some are random.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut rules = vec![Rule::Terminal('a'), Rule::Terminal('b')];
+    let mut level = vec![0, 1];
+    for level_size in [4, 6, 2].iter() {
+        let first = rules.len();
+        for _ in 0..*level_size {
+            let alternatives = (0..rng.range(1, 2))
+                .map(|_| vec![*rng.choose(&level), *rng.choose(&level)])
+                .collect();
+            rules.push(Rule::Alternatives(alternatives));
+        }
+        level = (first..rules.len()).collect();
+    }
+    let (rule42, rule31) = (level[0], level[1]);
+
+    // Rule numbers for the generated rules, avoiding the reserved ones
+    let mut numbers = (1..rules.len() + 40)
+        .filter(|n| !RESERVED.contains(n))
+        .collect::<Vec<usize>>();
+    rng.shuffle(&mut numbers);
+    numbers[rule42] = 42;
+    numbers[rule31] = 31;
+    let mut lines = vec![
+        "0: 8 11".to_string(),
+        "8: 42".to_string(),
+        "11: 42 31".to_string(),
+    ];
+    for (idx, rule) in rules.iter().enumerate() {
+        let text = match rule {
+            Rule::Terminal(c) => format!("\"{}\"", c),
+            Rule::Alternatives(alternatives) => alternatives
+                .iter()
+                .map(|seq| {
+                    let seq = seq.iter().map(|r| numbers[*r].to_string());
+                    seq.collect::<Vec<String>>().join(" ")
+                })
+                .collect::<Vec<String>>()
+                .join(" | "),
+        };
+        lines.push(format!("{}: {}", numbers[idx], text));
+    }
+    rng.shuffle(&mut lines);
+
+    lines.push(String::new());
+    for _ in 0..size.max(1) {
+        let mut message = String::new();
+        let (num42, num31) = match rng.range(0, 2) {
+            0 => (2, 1),
+            1 => {
+                let num31 = rng.range(1, 3);
+                (rng.range(num31 + 1, 5), num31)
+            }
+            _ => {
+                for _ in 0..rng.range(2, 6) * 8 {
+                    message.push(if rng.chance(0.5) { 'a' } else { 'b' });
+                }
+                (0, 0)
+            }
+        };
+        for _ in 0..num42 {
+            sample(rng, &rules, rule42, &mut message);
+        }
+        for _ in 0..num31 {
+            sample(rng, &rules, rule31, &mut message);
+        }
+        lines.push(message);
+    }
+    Generated::new(lines.join("\n") + "\n")
+}
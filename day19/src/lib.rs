@@ -0,0 +1,197 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+
+mod generate;
+
+#[derive(Clone, Copy, Debug)]
+enum Part {
+    Terminal(char),
+    Reference(usize),
+}
+
+type Rule = Vec<Vec<Part>>;
+
+fn read_file(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_string()).collect()
+}
+
+fn parse_part(line_no: usize, line: &str, token: &str) -> Result<Part, ParseError> {
+    let chars = token.chars().collect::<Vec<char>>();
+    match chars.first() {
+        Some('"') if chars.len() == 3 && chars[2] == '"' => Ok(Part::Terminal(chars[1])),
+        Some('0'..='9') => Ok(Part::Reference(parse_field(line_no, line, token)?)),
+        _ => {
+            let message = format!("syntax error '{}'", token);
+            Err(ParseError::at(line_no, line, token, &message))
+        }
+    }
+}
+
+fn parse_rules(lines: &[String]) -> Result<Vec<Rule>, ParseError> {
+    let mut parsed = Vec::new();
+    let mut references = Vec::new(); // line index, token, rule index
+    for (idx, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            break;
+        }
+        let (index, rule_text) = match line.split_once(": ") {
+            Some(index_rule) => index_rule,
+            None => {
+                let message = "expected '<index>: <rule>'";
+                return Err(ParseError::new(idx + 1, 1, line, message));
+            }
+        };
+        let index = parse_field::<usize>(idx + 1, line, index)?;
+        let mut rule = Rule::new();
+        for r in rule_text.split(" | ") {
+            let mut seq = Vec::new();
+            for t in r.split(' ') {
+                let part = parse_part(idx + 1, line, t)?;
+                if let Part::Reference(r) = part {
+                    references.push((idx, t, r));
+                }
+                seq.push(part);
+            }
+            rule.push(seq);
+        }
+        parsed.push((index, rule));
+    }
+
+    let num_rules = parsed.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+    let mut defined = vec![false; num_rules];
+    parsed.iter().for_each(|p| defined[p.0] = true);
+    for (idx, token, r) in references.iter() {
+        if *r >= num_rules || !defined[*r] {
+            let message = format!("reference to undefined rule {}", r);
+            return Err(ParseError::at(idx + 1, &lines[*idx], token, &message));
+        }
+    }
+
+    let mut rules = vec![Rule::new(); num_rules];
+    for (index, rule) in parsed.into_iter() {
+        rules[index] = rule;
+    }
+    Ok(rules)
+}
+
+fn parse_messages(lines: &[String]) -> Vec<Vec<char>> {
+    lines
+        .iter()
+        .skip_while(|l| !l.is_empty())
+        .skip(1)
+        .map(|l| l.chars().collect())
+        .collect()
+}
+
+fn check_with_rule(
+    rules: &[Rule],
+    message: &[char],
+    msg_pos: usize,
+    rule_idx: usize,
+) -> (usize, bool) {
+    let rule = match rules.get(rule_idx) {
+        Some(rule) => rule,
+        None => return (msg_pos, false),
+    };
+    if msg_pos >= message.len() {
+        return (msg_pos, false);
+    }
+    for seq in rule.iter() {
+        let mut ok = true;
+        let mut pos = msg_pos;
+        for p in seq.iter() {
+            if pos == message.len() {
+                ok = false;
+                break;
+            }
+            match p {
+                Part::Reference(idx) => {
+                    let res = check_with_rule(rules, message, pos, *idx);
+                    if !res.1 {
+                        ok = false;
+                        break;
+                    }
+                    pos = res.0;
+                }
+                Part::Terminal(c) => {
+                    if *c != message[pos] {
+                        ok = false;
+                        break;
+                    }
+                    pos += 1;
+                }
+            }
+        }
+        if ok {
+            return (pos, ok);
+        }
+    }
+    (msg_pos, false)
+}
+
+fn check_message(rules: &[Rule], message: &[char], rule_idx: usize) -> bool {
+    let res = check_with_rule(rules, message, 0, rule_idx);
+    res.0 == message.len() && res.1
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    let lines = read_file(input);
+    parse_rules(&lines)?;
+    Ok(parse_messages(&lines).len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let lines = read_file(input);
+    let rules = parse_rules(&lines)?;
+    let messages = parse_messages(&lines);
+    let num_ok = messages.iter().fold(0, |acc, message| {
+        if check_message(&rules, message, 0) {
+            acc + 1
+        } else {
+            acc
+        }
+    });
+    Ok(num_ok.into())
+}
+
+// This works because the only rule that is refering to rule 8 and 11 is
+// rule 0, which means this can be solved by turning rule 0 to "x*42 y*31"
+// where 2 <= x and 1 <= y < x.
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let lines = read_file(input);
+    let rules = parse_rules(&lines)?;
+    let messages = parse_messages(&lines);
+    let num_ok = messages.iter().fold(0, |acc, message| {
+        // i is large enough to match the longest message
+        for i in 2..100 {
+            let mut res_42 = (0, false);
+            for _ in 0..i {
+                res_42 = check_with_rule(&rules, message, res_42.0, 42);
+            }
+            let mut res_31 = res_42;
+            for _ in 0..i - 1 {
+                res_31 = check_with_rule(&rules, message, res_31.0, 31);
+                if res_31.0 >= message.len() {
+                    break;
+                }
+            }
+            if res_42.1 && res_31.1 && res_31.0 == message.len() {
+                return acc + 1;
+            }
+        }
+        acc
+    });
+    Ok(num_ok.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 19,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 460,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,120 @@
+use common::{Answer, Generated, Rng};
+use grid::Grid;
+use std::collections::HashSet;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+fn pixel(rng: &mut Rng) -> char {
+    if rng.chance(0.5) {
+        '#'
+    } else {
+        '.'
+    }
+}
+
+fn edge_value(lattice: &Grid<char>, cells: &[(i64, i64)]) -> (u32, u32) {
+    let bits = cells
+        .iter()
+        .map(|&(x, y)| {
+            if lattice.get(x, y) == Some(&'#') {
+                1
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<u32>>();
+    let value = bits.iter().fold(0, |e, b| (e << 1) | b);
+    let reversed = bits.iter().rev().fold(0, |e, b| (e << 1) | b);
+    (value, reversed)
+}
+
+/// Generates a square of about `size` tiles (between 9 and 144), rotated and
+/// flipped at random and in random order, with sea monsters in the image.
+///
+/// The tiles are cut from one large grid, overlapping by one row or column
+/// so that neighbours share their borders. Every border is different from all
+/// other borders, also when flipped, so the tiles only fit together one way
+/// and the answer to part 1 is known. There are only so many 10 pixel
+/// borders, which is why there can't be more than 144 tiles.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let side = ((size as f64).sqrt() as usize).max(3);
+    let lattice_size = side * 9 + 1;
+    let mut lattice = Grid::new(lattice_size, lattice_size, '.');
+    for y in 0..lattice_size as i64 {
+        for x in 0..lattice_size as i64 {
+            lattice.set(x, y, pixel(rng));
+        }
+    }
+
+    // Redraw the inside of each border until it's unique. The corners are
+    // shared by up to four borders and are left as they are.
+    let mut used = HashSet::new();
+    for line in 0..=side as i64 {
+        for segment in 0..side as i64 {
+            for horizontal in [true, false].iter() {
+                let cells = (0..10)
+                    .map(|i| {
+                        let along = segment * 9 + i;
+                        if *horizontal {
+                            (along, line * 9)
+                        } else {
+                            (line * 9, along)
+                        }
+                    })
+                    .collect::<Vec<(i64, i64)>>();
+                loop {
+                    for &(x, y) in cells[1..9].iter() {
+                        lattice.set(x, y, pixel(rng));
+                    }
+                    let (value, reversed) = edge_value(&lattice, &cells);
+                    if value != reversed && used.insert(value.min(reversed)) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    // Sea monsters in the image, which is the lattice without the borders
+    let image_size = side as i64 * 8;
+    for _ in 0..(side * side / 4).max(1) {
+        let x = rng.range(0, image_size as u64 - 20) as i64;
+        let y = rng.range(0, image_size as u64 - 3) as i64;
+        for (my, row) in MONSTER.iter().enumerate() {
+            for (mx, c) in row.chars().enumerate() {
+                if c == '#' {
+                    let (ix, iy) = (x + mx as i64, y + my as i64);
+                    lattice.set(ix / 8 * 9 + 1 + ix % 8, iy / 8 * 9 + 1 + iy % 8, '#');
+                }
+            }
+        }
+    }
+
+    let mut numbers = (1000..10000).collect::<Vec<u64>>();
+    rng.shuffle(&mut numbers);
+    let corners = [(0, 0), (side - 1, 0), (0, side - 1), (side - 1, side - 1)];
+    let mut corner_product = 1;
+    let mut tiles = Vec::new();
+    for ty in 0..side {
+        for tx in 0..side {
+            let number = numbers[ty * side + tx];
+            if corners.contains(&(tx, ty)) {
+                corner_product *= number;
+            }
+            let tile = lattice
+                .sub_grid(tx * 9, ty * 9, 10, 10)
+                .orientation(rng.index(8));
+            tiles.push(format!("Tile {}:\n{}", number, tile));
+        }
+    }
+    rng.shuffle(&mut tiles);
+    Generated {
+        input: tiles.join("\n"),
+        part1: Some(Answer::from(corner_product)),
+        part2: None,
+    }
+}
@@ -0,0 +1,345 @@
+use common::{par, parse_field, Answer, Generator, ParseError, Puzzle};
+use grid::Grid;
+
+mod generate;
+
+const TOP: usize = 0;
+const RIGHT: usize = 1;
+const BOTTOM: usize = 2;
+const LEFT: usize = 3;
+
+// Stores edges as 10-bit integers. It wasn't as simple as it first seemed. It
+// had probably been better to rotate/mirror the tile data instead, especially
+// since this had to be done for part 2 anyway.
+struct Tile {
+    number: u64,
+    edges: [[u32; 4]; 8],
+    data: Grid<char>,
+}
+
+struct Placement {
+    tile: usize,
+    orientation: usize,
+    x: i32,
+    y: i32,
+}
+
+fn parse_edges(data: &Grid<char>) -> [u32; 4] {
+    let edge = |pixels: &mut dyn Iterator<Item = &char>| {
+        pixels.fold(0, |e, c| (e << 1) | if *c == '#' { 1 } else { 0 })
+    };
+    let (right, left) = (data.column(9), data.column(0));
+    let t = edge(&mut data.row(0).iter());
+    let r = edge(&mut right.iter());
+    let b = edge(&mut data.row(9).iter().rev());
+    let l = edge(&mut left.iter().rev());
+    [t, r, b, l]
+}
+
+fn rotate_tile(edges: &[u32; 4], num_rotates: u32) -> [u32; 4] {
+    if num_rotates == 0 {
+        *edges
+    } else {
+        rotate_tile(
+            &[edges[LEFT], edges[TOP], edges[RIGHT], edges[BOTTOM]],
+            num_rotates - 1,
+        )
+    }
+}
+
+fn flip_bl_edges(edges: &[u32; 4]) -> [u32; 4] {
+    fn flip(edge: u32) -> u32 {
+        let mut flipped = 0;
+        for i in 0..10 {
+            let bit = (edge & (1 << i)) >> i;
+            flipped = (flipped << 1) | bit;
+        }
+        flipped
+    }
+    [
+        edges[TOP],
+        edges[RIGHT],
+        flip(edges[BOTTOM]),
+        flip(edges[LEFT]),
+    ]
+}
+
+// `line_no` is the line number of the tile header
+fn parse_tile(line_no: usize, lines: &[&str]) -> Result<Tile, ParseError> {
+    let header = lines[0];
+    let number = match header.strip_prefix("Tile ") {
+        Some(rest) if rest.ends_with(':') => parse_field(line_no, header, &rest[..rest.len() - 1])?,
+        _ => {
+            return Err(ParseError::new(
+                line_no,
+                1,
+                header,
+                "expected 'Tile <number>:'",
+            ))
+        }
+    };
+    let rows = lines[1..lines.len().min(11)].join("\n");
+    let data = grid::parse(&rows).map_err(|err| ParseError {
+        line: err.line + line_no,
+        ..err
+    })?;
+    if data.height() != 10 {
+        let line = lines.get(data.height() + 1).copied().unwrap_or("");
+        let message = format!("expected 10 rows of pixels, found {}", data.height());
+        return Err(ParseError::new(
+            line_no + data.height() + 1,
+            1,
+            line,
+            &message,
+        ));
+    }
+    if data.width() != 10 {
+        let message = format!("expected 10 pixels, found {}", data.width());
+        return Err(ParseError::new(line_no + 1, 1, lines[1], &message));
+    }
+    if let Some(line) = lines.get(11).filter(|line| !line.is_empty()) {
+        return Err(ParseError::new(
+            line_no + 11,
+            1,
+            line,
+            "expected an empty line",
+        ));
+    }
+    let edges = parse_edges(&data);
+    let edgesm = parse_edges(&data.flip_horizontal());
+    let all_edges = [
+        flip_bl_edges(&edges),
+        flip_bl_edges(&rotate_tile(&edges, 1)),
+        flip_bl_edges(&rotate_tile(&edges, 2)),
+        flip_bl_edges(&rotate_tile(&edges, 3)),
+        flip_bl_edges(&edgesm),
+        flip_bl_edges(&rotate_tile(&edgesm, 1)),
+        flip_bl_edges(&rotate_tile(&edgesm, 2)),
+        flip_bl_edges(&rotate_tile(&edgesm, 3)),
+    ];
+    Ok(Tile {
+        number,
+        edges: all_edges,
+        data,
+    })
+}
+
+fn read_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    input
+        .lines()
+        .collect::<Vec<&str>>()
+        .chunks(12)
+        .enumerate()
+        .map(|(idx, lines)| parse_tile(idx * 12 + 1, lines))
+        .collect()
+}
+
+fn get_placement(placements: &[Placement], x: i32, y: i32) -> Option<&Placement> {
+    placements.iter().find(|p| p.x == x && p.y == y)
+}
+
+fn edges_match(tiles: &[Tile], p1: &Placement, p2: &Placement, e1: usize, e2: usize) -> bool {
+    let edges1 = &tiles[p1.tile].edges[p1.orientation];
+    let edges2 = &tiles[p2.tile].edges[p2.orientation];
+    edges1[e1] == edges2[e2]
+}
+
+fn placement_ok(tiles: &[Tile], placements: &[Placement], placement: &Placement) -> bool {
+    if let Some(p) = get_placement(placements, placement.x - 1, placement.y) {
+        if !edges_match(tiles, p, placement, RIGHT, LEFT) {
+            return false;
+        }
+    }
+    if let Some(p) = get_placement(placements, placement.x + 1, placement.y) {
+        if !edges_match(tiles, p, placement, LEFT, RIGHT) {
+            return false;
+        }
+    }
+    if let Some(p) = get_placement(placements, placement.x, placement.y - 1) {
+        if !edges_match(tiles, p, placement, BOTTOM, TOP) {
+            return false;
+        }
+    }
+    if let Some(p) = get_placement(placements, placement.x, placement.y + 1) {
+        if !edges_match(tiles, p, placement, TOP, BOTTOM) {
+            return false;
+        }
+    }
+    true
+}
+
+fn next_position(num_tiles: usize, x: i32, y: i32) -> (i32, i32) {
+    let size = (num_tiles as f32).sqrt() as i32;
+    ((x + 1) % size, y + (x + 1) / size)
+}
+
+fn place_tiles(tiles: &[Tile], placements: &mut Vec<Placement>, x: i32, y: i32) -> bool {
+    if tiles.len() == placements.len() {
+        return true;
+    }
+    for tile in 0..tiles.len() {
+        if placements.iter().position(|t| t.tile == tile).is_some() {
+            continue;
+        }
+        for orientation in 0..8 {
+            let placement = Placement {
+                tile,
+                orientation,
+                x,
+                y,
+            };
+            if placement_ok(tiles, placements, &placement) {
+                placements.push(placement);
+                let (nx, ny) = next_position(tiles.len(), x, y);
+                if place_tiles(tiles, placements, nx, ny) {
+                    return true;
+                }
+                placements.pop();
+            }
+        }
+    }
+    false
+}
+
+// Tries each tile and orientation in the top left corner, and places the rest
+// of the tiles from there. The first start that works is used, so the result is
+// the same whether the starts are tried in parallel or not.
+fn find_placements(tiles: &[Tile]) -> Option<Vec<Placement>> {
+    let starts = (0..tiles.len())
+        .flat_map(|tile| (0..8).map(move |orientation| (tile, orientation)))
+        .collect::<Vec<(usize, usize)>>();
+    par::find_map_first(&starts, |&(tile, orientation)| {
+        let mut placements = vec![Placement {
+            tile,
+            orientation,
+            x: 0,
+            y: 0,
+        }];
+        let (x, y) = next_position(tiles.len(), 0, 0);
+        if place_tiles(tiles, &mut placements, x, y) {
+            Some(placements)
+        } else {
+            None
+        }
+    })
+}
+
+fn assemble_image(tiles: &[Tile], placements: &[Placement]) -> Grid<char> {
+    let size = (tiles.len() as f32).sqrt() as usize;
+    let mut image = Grid::new(size * 8, size * 8, '.');
+    for y in 0..size {
+        for x in 0..size {
+            if let Some(p) = get_placement(placements, x as i32, y as i32) {
+                let tile = tiles[p.tile].data.orientation(p.orientation);
+                image.paste(x * 8, y * 8, &tile.sub_grid(1, 1, 8, 8));
+            }
+        }
+    }
+    image
+}
+
+fn mark_sea_monster(image: &mut Grid<char>, monster: &Grid<char>, x: i64, y: i64) {
+    let parts = monster
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|((mx, my), _)| (x + mx, y + my))
+        .collect::<Vec<(i64, i64)>>();
+    if parts.iter().all(|&(x, y)| image.get(x, y) == Some(&'#')) {
+        for (x, y) in parts {
+            image.set(x, y, 'O');
+        }
+    }
+}
+
+fn mark_sea_monsters(image: &mut Grid<char>) {
+    let monster = Grid::from_rows(
+        [
+            "                  # ",
+            "#    ##    ##    ###",
+            " #  #  #  #  #  #   ",
+        ]
+        .iter()
+        .map(|row| row.chars().collect())
+        .collect(),
+    );
+    // Marks the image in all 8 orientations. After 4 rotations it's back
+    // where it started and gets flipped for the other 4.
+    for orientation in 0..8 {
+        if orientation == 4 {
+            *image = image.flip_horizontal();
+        }
+        // No monster fits in an image built from only a few tiles
+        if let (Some(last_y), Some(last_x)) = (
+            image.height().checked_sub(monster.height()),
+            image.width().checked_sub(monster.width()),
+        ) {
+            for y in 0..=last_y as i64 {
+                for x in 0..=last_x as i64 {
+                    mark_sea_monster(image, &monster, x, y);
+                }
+            }
+        }
+        *image = image.rotate_right();
+    }
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_tiles(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let tiles = read_tiles(input)?;
+    if let Some(placements) = find_placements(&tiles) {
+        let size = (tiles.len() as f32).sqrt() as usize;
+        let ans = tiles[placements[0].tile].number
+            * tiles[placements[size - 1].tile].number
+            * tiles[placements[size * (size - 1)].tile].number
+            * tiles[placements[size * size - 1].tile].number;
+        Ok(ans.into())
+    } else {
+        Ok(Answer::NotFound)
+    }
+}
+
+/*
+fn debug_draw_image(image: &Grid<char>) {
+    image.rows().for_each(|l| {
+        l.iter().for_each(|&c| {
+            if c == '#' {
+                print!("██");
+            } else if c == 'O' {
+                //print!("░░");
+                print!("🐊");
+            } else {
+                print!("  ")
+            }
+        });
+        println!("");
+    });
+} */
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let tiles = read_tiles(input)?;
+    if let Some(placements) = find_placements(&tiles) {
+        let mut image = assemble_image(&tiles, &placements);
+        mark_sea_monsters(&mut image);
+        let ans = image.iter().filter(|(_, c)| **c == '#').count();
+        // debug_draw_image(&image);
+        Ok(ans.into())
+    } else {
+        Ok(Answer::NotFound)
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 20,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 144,
+        max_size: Some(144),
+        generate: generate::generate,
+    },
+};
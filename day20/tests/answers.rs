@@ -0,0 +1,23 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day20::{solve_part1, solve_part2};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "example.txt",
+        input: include_str!("../example.txt"),
+        part1: Some(Number(20899048083289)),
+        part2: Some(Number(273)),
+    },
+    Expected {
+        name: "input.txt",
+        input: include_str!("../input.txt"),
+        part1: Some(Number(29125888761511)),
+        part2: Some(Number(2219)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
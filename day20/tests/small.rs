@@ -0,0 +1,21 @@
+use common::Answer::Number;
+use day20::{solve_part1, solve_part2};
+
+#[test]
+fn single_tile() {
+    let tile = "Tile 7:
+#.........
+.#........
+..#.......
+...#......
+....#.....
+.....#....
+......#...
+.......#..
+........#.
+.........#
+";
+    assert_eq!(solve_part1(tile).unwrap(), Number(7 * 7 * 7 * 7));
+    // The image is the tile without its border, too small for a monster
+    assert_eq!(solve_part2(tile).unwrap(), Number(8));
+}
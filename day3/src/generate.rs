@@ -0,0 +1,14 @@
+use common::{Generated, Rng};
+
+/// Generates a map with `size` rows of 31 squares, about a fifth of them
+/// trees.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        for _ in 0..31 {
+            input.push(if rng.chance(0.2) { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    Generated::new(input)
+}
@@ -0,0 +1,124 @@
+use common::{par, Answer, Generator, ParseError, Puzzle};
+use grid::Grid;
+use wrap::{Wrap, WrapMode};
+
+mod generate;
+pub mod packed;
+pub mod render;
+pub mod route;
+pub mod search;
+pub mod slope;
+pub mod terrain;
+pub mod wrap;
+
+/// The slope of `.` open squares and `#` trees, which repeats to the right
+/// unless it's given another `Wrap`.
+pub struct Map {
+    grid: Grid<char>,
+    wrap: Wrap,
+}
+
+impl Map {
+    fn new(grid: Grid<char>) -> Self {
+        Self {
+            grid,
+            wrap: Wrap::default(),
+        }
+    }
+
+    /// Returns the map wrapping as given by `wrap`. A map wrapping
+    /// vertically must have a step limit.
+    pub fn with_wrap(self, wrap: Wrap) -> Self {
+        assert!(
+            wrap.y == WrapMode::Bounded || wrap.max_steps.is_some(),
+            "a map wrapping vertically needs a step limit"
+        );
+        Self { wrap, ..self }
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Returns the character of the square at `(x, y)`, or `None` past an
+    /// edge that doesn't wrap.
+    pub fn square(&self, x: i32, y: i32) -> Option<char> {
+        let x = self.wrap.x.apply(x as i64, self.grid.width())?;
+        let y = self.wrap.y.apply(y as i64, self.grid.height())?;
+        Some(self.grid.row(y)[x])
+    }
+
+    /// Returns whether there's a tree at `(x, y)`, or `None` past an edge
+    /// that doesn't wrap, such as below the map.
+    pub fn is_tree(&self, x: i32, y: i32) -> Option<bool> {
+        self.square(x, y).map(|c| c == '#')
+    }
+}
+
+pub fn read_map(input: &str) -> Result<Map, ParseError> {
+    Ok(Map::new(grid::parse(input)?))
+}
+
+/// Counts the trees hit going `slopex` right and `slopey` down at a time from
+/// the top left corner until the bottom of the map, or until the map's step
+/// limit.
+pub fn count_trees(map: &Map, slopex: i32, slopey: i32) -> u64 {
+    let mut x = 0;
+    let mut y = 0;
+    let mut count = 0;
+    let mut steps = 0;
+    loop {
+        let is_tree = map.is_tree(x, y);
+        if let Some(is_tree) = is_tree {
+            if is_tree {
+                count += 1;
+            }
+        } else {
+            break;
+        }
+        if Some(steps) == map.wrap.max_steps {
+            break;
+        }
+        steps += 1;
+        x += slopex;
+        y += slopey;
+    }
+    count as u64
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_map(input)?.grid.height())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let map = read_map(input)?;
+    Ok(count_trees(&map, 3, 1).into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let map = read_map(input)?;
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    let counts = par::map(&slopes, |&(x, y)| count_trees(&map, x, y));
+    Ok(counts.iter().product::<u64>().into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 3,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 323,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,175 @@
+use crate::slope::Slope;
+use common::cli::{set_source, Source};
+use common::{Error, ParseError};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+pub const COUNT_USAGE: &str = "Usage: day3 count <input file|-> [count options]
+
+Counts the trees on several slopes in one pass over the map, reading it a
+line at a time, so maps too big to fit in memory can be counted.
+
+Count options:
+    --input <text>          Use the given text as input instead of a file
+    --slope <right/down>    Count the trees on this slope; can be given
+                            several times (default the slopes of part 2)";
+
+/// Parses one row of the map into words with a bit set for each tree,
+/// starting from the lowest bit of the first word. `width` is the width of
+/// the earlier rows, if any.
+fn parse_row(line_no: usize, line: &str, width: Option<usize>) -> Result<Vec<u64>, ParseError> {
+    let mut words = vec![0u64; line.len().div_ceil(64)];
+    let mut len = 0;
+    for (col, c) in line.chars().enumerate() {
+        match c {
+            '#' => words[col / 64] |= 1 << (col % 64),
+            '.' => {}
+            _ => {
+                let message = format!("unexpected character '{}'", c);
+                return Err(ParseError::new(line_no, col + 1, line, &message));
+            }
+        }
+        len += 1;
+    }
+    match width {
+        Some(width) if width != len => {
+            let message = format!("expected {} columns, found {}", width, len);
+            Err(ParseError::new(line_no, 1, line, &message))
+        }
+        None if len == 0 => Err(ParseError::new(line_no, 1, line, "empty line")),
+        _ => Ok(words),
+    }
+}
+
+fn has_tree(words: &[u64], x: usize) -> bool {
+    words[x / 64] & (1 << (x % 64)) != 0
+}
+
+/// A map stored with one bit per square, using a 32nd of the memory of
+/// `Map`.
+pub struct PackedMap {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl PackedMap {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut map = PackedMap {
+            width: 0,
+            height: 0,
+            words_per_row: 0,
+            words: Vec::new(),
+        };
+        for (idx, line) in input.lines().enumerate() {
+            let width = if idx == 0 { None } else { Some(map.width) };
+            let row = parse_row(idx + 1, line, width)?;
+            if idx == 0 {
+                map.width = line.len();
+                map.words_per_row = row.len();
+            }
+            map.words.extend(row);
+            map.height += 1;
+        }
+        Ok(map)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Like `Map::is_tree`.
+    pub fn is_tree(&self, x: i32, y: i32) -> Option<bool> {
+        if y >= 0 && (y as usize) < self.height {
+            let row = y as usize * self.words_per_row;
+            let x = (x as i64).rem_euclid(self.width as i64) as usize;
+            Some(has_tree(&self.words[row..row + self.words_per_row], x))
+        } else {
+            None
+        }
+    }
+}
+
+/// Counts the trees on each of `slopes` like `count_trees`, but in one pass
+/// over the lines of `reader`, keeping only the current line in memory.
+/// `name` names the input in errors.
+pub fn count_trees_streaming(
+    reader: impl BufRead,
+    name: &str,
+    slopes: &[Slope],
+) -> Result<Vec<u64>, Error> {
+    let mut counts = vec![0; slopes.len()];
+    let mut width = None;
+    for (y, line) in reader.lines().enumerate() {
+        let line = line.map_err(|err| Error::Io(name.to_string(), err))?;
+        let row = parse_row(y + 1, &line, width).map_err(|err| err.in_file(name))?;
+        let row_width = *width.get_or_insert(line.len()) as i64;
+        for (slope, count) in slopes.iter().zip(counts.iter_mut()) {
+            let down = slope.down as usize;
+            if y % down == 0 {
+                let x = ((y / down) as i64 * slope.right as i64).rem_euclid(row_width);
+                if has_tree(&row, x as usize) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    Ok(counts)
+}
+
+pub struct CountOptions {
+    pub source: Option<Source>,
+    pub slopes: Vec<Slope>,
+}
+
+pub fn parse_count_options(args: &[String]) -> Result<CountOptions, String> {
+    let mut options = CountOptions {
+        source: None,
+        slopes: Vec::new(),
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                "--slope" => options.slopes.push(value.parse()?),
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    if options.slopes.is_empty() {
+        options.slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .map(|&(right, down)| Slope::new(right, down))
+            .collect();
+    }
+    Ok(options)
+}
+
+pub fn run_count(options: &CountOptions) -> Result<(), Error> {
+    let source = options.source.as_ref().ok_or(Error::NoInput)?;
+    let stdin = io::stdin();
+    let reader: Box<dyn BufRead> = match source {
+        Source::File(file) => Box::new(BufReader::new(
+            File::open(file).map_err(|err| Error::Io(file.clone(), err))?,
+        )),
+        Source::Stdin => Box::new(stdin.lock()),
+        Source::Text(text) => Box::new(text.as_bytes()),
+    };
+    let counts = count_trees_streaming(reader, source.name(), &options.slopes)?;
+    for (slope, count) in options.slopes.iter().zip(counts) {
+        println!(
+            "right {:>3} down {:>3}  {} trees",
+            slope.right, slope.down, count
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::route::safest_route;
+use crate::slope::{visited, Mode, Slope};
+use crate::wrap::{Wrap, WrapMode};
+use crate::{read_map, Map, PUZZLE};
+use common::cli::{load_input, set_source, Source};
+use common::Error;
+use std::collections::HashSet;
+use std::ops::Range;
+
+pub const RENDER_USAGE: &str = "Usage: day3 render <input file|-> [render options]
+
+Prints the map, repeated as far as the path goes, with the trees hit marked
+X and the open squares passed marked O.
+
+Render options:
+    --input <text>          Use the given text as input instead of a file
+    --slope <right/down>    Go down this slope (default 3/1)
+    --swept                 Mark every square the slope passes through
+    --route                 Follow the route with the fewest trees instead
+    --colour                Colour the marks with ANSI escape codes
+    --wrap-x <mode>         How the map wraps sideways: bounded, toroidal
+                            (default) or mirrored
+    --wrap-y <mode>         How the map wraps downwards (default bounded)
+    --max-steps <N>         Stop after N steps of the slope, also with
+                            --swept, needed when wrapping downwards";
+
+/// The path to draw on the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Path {
+    Slope(Slope, Mode),
+    Route,
+}
+
+pub struct RenderOptions {
+    pub source: Option<Source>,
+    pub path: Path,
+    pub colour: bool,
+    pub wrap: Wrap,
+}
+
+pub fn parse_render_options(args: &[String]) -> Result<RenderOptions, String> {
+    let mut source = None;
+    let mut slope = None;
+    let mut mode = Mode::Stepped;
+    let mut route = false;
+    let mut colour = false;
+    let mut wrap = Wrap::default();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--swept" => mode = Mode::Swept,
+            "--route" => route = true,
+            "--colour" => colour = true,
+            "--slope" | "--input" | "--wrap-x" | "--wrap-y" | "--max-steps" => {
+                let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+                match arg.as_str() {
+                    "--slope" => slope = Some(value.parse::<Slope>()?),
+                    "--wrap-x" => wrap.x = value.parse()?,
+                    "--wrap-y" => wrap.y = value.parse()?,
+                    "--max-steps" => {
+                        wrap.max_steps = Some(
+                            value
+                                .parse::<usize>()
+                                .map_err(|_| format!("Invalid number of steps: {}", value))?,
+                        )
+                    }
+                    _ => set_source(&mut source, Source::Text(value.clone()))?,
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ => set_source(&mut source, Source::from_arg(arg))?,
+        }
+    }
+    if wrap.y != WrapMode::Bounded && wrap.max_steps.is_none() {
+        return Err("--max-steps must be given when wrapping downwards".to_string());
+    }
+    let path = match (route, slope) {
+        (true, None) if mode == Mode::Stepped => Path::Route,
+        (true, _) => return Err("--route can't be combined with --slope or --swept".to_string()),
+        (false, slope) => Path::Slope(slope.unwrap_or(Slope::new(3, 1)), mode),
+    };
+    Ok(RenderOptions {
+        source,
+        path,
+        colour,
+        wrap,
+    })
+}
+
+/// Returns the positions along an axis of whole copies of a map `size`
+/// squares long, from the original to every one of `positions`.
+fn copies(positions: impl Iterator<Item = i32>, size: usize) -> Range<i32> {
+    let size = size as i32;
+    if size == 0 {
+        return 0..0;
+    }
+    let (min, max) = positions.fold((0, size - 1), |(min, max), pos| {
+        (min.min(pos), max.max(pos))
+    });
+    min.div_euclid(size) * size..(max.div_euclid(size) + 1) * size
+}
+
+/// Renders the map with the squares in `path` marked, repeating the map as
+/// far as it wraps to cover the whole path. Unmarked squares keep their own
+/// characters.
+pub fn render(map: &Map, path: &[(i32, i32)], colour: bool) -> String {
+    let marked = path.iter().copied().collect::<HashSet<(i32, i32)>>();
+    let columns = copies(path.iter().map(|&(x, _)| x), map.width());
+    let mut text = String::new();
+    for y in copies(path.iter().map(|&(_, y)| y), map.height()) {
+        for x in columns.clone() {
+            if !marked.contains(&(x, y)) {
+                text.push(map.square(x, y).unwrap());
+                continue;
+            }
+            let cell = match (map.is_tree(x, y) == Some(true), colour) {
+                (true, false) => "X",
+                (false, false) => "O",
+                (true, true) => "\x1b[1;31mX\x1b[0m",
+                (false, true) => "\x1b[32mO\x1b[0m",
+            };
+            text.push_str(cell);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+pub fn run_render(options: &RenderOptions) -> Result<(), Error> {
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let map = read_map(&input)
+        .map_err(|err| err.in_file(&name))?
+        .with_wrap(options.wrap);
+    let path = match options.path {
+        Path::Slope(slope, mode) => visited(&map, slope, mode),
+        Path::Route => safest_route(&map, Some(0)).map_or(Vec::new(), |route| route.path),
+    };
+    print!("{}", render(&map, &path, options.colour));
+    let trees = path
+        .iter()
+        .filter(|&&(x, y)| map.is_tree(x, y) == Some(true))
+        .count();
+    println!("{} trees hit", trees);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::wrap::WrapMode;
+use crate::Map;
+
+/// A way down the map and the number of trees on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub trees: u64,
+    /// One square per row from the top to the bottom. The x positions aren't
+    /// wrapped, so going left past the edge gives negative positions.
+    pub path: Vec<(i32, i32)>,
+}
+
+/// Moves from one row to the next, in the order they're preferred when
+/// routes hit the same number of trees.
+const MOVES: [i32; 3] = [0, -1, 1];
+
+/// Finds a route from the top row to the bottom row that hits the fewest
+/// trees, moving down, down-left or down-right each step. It starts in column
+/// `start`, or in whichever column of the top row is best if `start` is
+/// `None`. The route follows the map's horizontal wrapping, but always ends
+/// on the bottom row. Returns `None` for an empty map, or if `start` is off
+/// a bounded map.
+pub fn safest_route(map: &Map, start: Option<i32>) -> Option<Route> {
+    let width = map.width() as i32;
+    if width == 0 || map.height() == 0 {
+        return None;
+    }
+    let wrap = map.wrap().x;
+    // The columns repeat every `period` squares, which for a bounded map is
+    // just the one copy
+    let period = if wrap == WrapMode::Mirrored {
+        2 * width
+    } else {
+        width
+    };
+    let column = |x: i32| match wrap {
+        WrapMode::Bounded if x < 0 || x >= width => None,
+        _ => Some(x.rem_euclid(period) as usize),
+    };
+    let tree_cost = |x: i32, y: i32| map.is_tree(x, y).map_or(0, u64::from);
+    // The fewest trees on the way to each column of the current row
+    let mut trees = (0..period)
+        .map(|x| match start {
+            Some(start) if Some(x as usize) != column(start) => None,
+            _ => Some(tree_cost(x, 0)),
+        })
+        .collect::<Vec<Option<u64>>>();
+    // The move taken into each square of the rows below the top one
+    let mut moves: Vec<Vec<i32>> = Vec::new();
+    for y in 1..map.height() as i32 {
+        let mut row_trees = vec![None; period as usize];
+        let mut row_moves = vec![0; period as usize];
+        for x in 0..period {
+            let best = MOVES
+                .iter()
+                .filter_map(|&dx| column(x - dx).and_then(|c| trees[c]).map(|t| (t, dx)))
+                .min_by_key(|&(t, _)| t);
+            if let Some((t, dx)) = best {
+                row_trees[x as usize] = Some(t + tree_cost(x, y));
+                row_moves[x as usize] = dx;
+            }
+        }
+        trees = row_trees;
+        moves.push(row_moves);
+    }
+
+    let (end, &least) = trees
+        .iter()
+        .enumerate()
+        .filter_map(|(x, t)| t.as_ref().map(|t| (x, t)))
+        .min_by_key(|&(_, t)| *t)?;
+    // Follow the moves back up, then shift the path so it starts at
+    // `start`
+    let mut x = end as i32;
+    let mut path = vec![(x, map.height() as i32 - 1)];
+    for (y, row_moves) in moves.iter().enumerate().rev() {
+        x -= row_moves[x.rem_euclid(period) as usize];
+        path.push((x, y as i32));
+    }
+    path.reverse();
+    let first = start.unwrap_or_else(|| path[0].0.rem_euclid(period));
+    let shift = first - path[0].0;
+    let path = path.iter().map(|&(x, y)| (x + shift, y)).collect();
+    Some(Route { trees: least, path })
+}
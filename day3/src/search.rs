@@ -0,0 +1,106 @@
+use crate::slope::Slope;
+use crate::{count_trees, read_map, Map, PUZZLE};
+use common::cli::{load_input, set_source, Source};
+use common::{par, Error};
+
+pub const SEARCH_USAGE: &str = "Usage: day3 search <input file|-> [search options]
+
+Counts the trees on every slope going at most --max squares right or left
+and at most --max squares down at a time, and lists the slopes with the
+fewest and the most trees. Slopes tied with the last one listed are listed
+too.
+
+Search options:
+    --input <text>          Use the given text as input instead of a file
+    --max <N>               Largest step in either direction (default 7)
+    --top <K>               Number of slopes to list each way (default 5)";
+
+pub struct SearchOptions {
+    pub source: Option<Source>,
+    pub max: i32,
+    pub top: usize,
+}
+
+pub fn parse_search_options(args: &[String]) -> Result<SearchOptions, String> {
+    let mut options = SearchOptions {
+        source: None,
+        max: 7,
+        top: 5,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                "--max" => {
+                    options.max = match value.parse::<i32>() {
+                        Ok(max) if max > 0 => max,
+                        _ => return Err(format!("Invalid maximum step: {}", value)),
+                    }
+                }
+                "--top" => {
+                    options.top = match value.parse::<usize>() {
+                        Ok(top) if top > 0 => top,
+                        _ => return Err(format!("Invalid number of slopes: {}", value)),
+                    }
+                }
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+/// Counts the trees on all slopes with steps of at most `max` in each
+/// direction, and returns them from the fewest trees to the most. Equal
+/// counts are ordered by slope.
+pub fn rank_slopes(map: &Map, max: i32) -> Vec<(Slope, u64)> {
+    let slopes = (1..=max)
+        .flat_map(|down| (-max..=max).map(move |right| Slope::new(right, down)))
+        .collect::<Vec<Slope>>();
+    let counts = par::map(&slopes, |slope| count_trees(map, slope.right, slope.down));
+    let mut ranked = slopes
+        .into_iter()
+        .zip(counts)
+        .collect::<Vec<(Slope, u64)>>();
+    ranked.sort_by_key(|&(slope, trees)| (trees, slope.down, slope.right));
+    ranked
+}
+
+/// Returns the first `k` entries of `ranked`, along with any later entries
+/// with the same number of trees as the last of them.
+pub fn top_k(ranked: &[(Slope, u64)], k: usize) -> &[(Slope, u64)] {
+    let mut end = k.min(ranked.len());
+    while end > 0 && end < ranked.len() && ranked[end].1 == ranked[end - 1].1 {
+        end += 1;
+    }
+    &ranked[..end]
+}
+
+fn print_ranking(title: &str, ranking: &[(Slope, u64)]) {
+    println!("{}:", title);
+    let mut rank = 0;
+    for (idx, (slope, trees)) in ranking.iter().enumerate() {
+        // Tied slopes share a rank
+        if idx == 0 || ranking[idx - 1].1 != *trees {
+            rank = idx + 1;
+        }
+        println!(
+            "{:>4}. right {:>3} down {:>3}  {} trees",
+            rank, slope.right, slope.down, trees
+        );
+    }
+}
+
+pub fn run_search(options: &SearchOptions) -> Result<(), Error> {
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let map = read_map(&input).map_err(|err| err.in_file(&name))?;
+    let mut ranked = rank_slopes(&map, options.max);
+    print_ranking("Fewest trees", top_k(&ranked, options.top));
+    ranked.sort_by_key(|&(slope, trees)| (u64::MAX - trees, slope.down, slope.right));
+    print_ranking("Most trees", top_k(&ranked, options.top));
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::{count_trees, Map};
+use std::str::FromStr;
+
+/// A slope of `right` columns for every `down` rows, so `Slope::new(1, 3)`
+/// goes one column right every three rows. `right` may be negative to go
+/// left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slope {
+    pub right: i32,
+    pub down: i32,
+}
+
+impl Slope {
+    pub fn new(right: i32, down: i32) -> Self {
+        assert!(down > 0, "a slope must go down");
+        Self { right, down }
+    }
+}
+
+impl FromStr for Slope {
+    type Err = String;
+
+    /// Parses `right/down`, or just `right` to go one row down at a time.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = match s.split_once('/') {
+            Some((right, down)) => (right.parse::<i32>(), down.parse::<i32>()),
+            None => (s.parse::<i32>(), Ok(1)),
+        };
+        match (right, down) {
+            (Ok(right), Ok(down)) if down > 0 => Ok(Slope::new(right, down)),
+            _ => Err(format!("Invalid slope: {}", s)),
+        }
+    }
+}
+
+/// Which squares count as visited when going down a slope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Only the squares landed on after each whole step of the slope, like
+    /// `count_trees`.
+    Stepped,
+    /// Every square the straight line from the centre of the top left square
+    /// passes through. Where the line goes exactly through a corner, it goes
+    /// on diagonally without touching the squares beside the corner.
+    Swept,
+}
+
+/// Returns the squares visited going down `slope` from the top left corner,
+/// in order, until the bottom of the map or the map's step limit, which in
+/// both modes ends the course at the square the last whole step lands on.
+/// The positions aren't wrapped.
+pub fn visited(map: &Map, slope: Slope, mode: Mode) -> Vec<(i32, i32)> {
+    let max_steps = map.wrap().max_steps;
+    match mode {
+        Mode::Stepped => (0..)
+            .map(|step| (step * slope.right, step * slope.down))
+            .take_while(|&(x, y)| map.is_tree(x, y).is_some())
+            .take(max_steps.map_or(usize::MAX, |steps| steps + 1))
+            .collect(),
+        Mode::Swept => swept(map, slope, max_steps),
+    }
+}
+
+fn swept(map: &Map, slope: Slope, max_steps: Option<usize>) -> Vec<(i32, i32)> {
+    let right = slope.right.unsigned_abs() as i64;
+    let down = slope.down as i64;
+    // The line goes on no further than the square of the last whole step
+    let (last_x, last_y) = match max_steps {
+        Some(steps) => (steps as i64 * right, steps as i64 * down),
+        None => (i64::MAX, i64::MAX),
+    };
+    let (mut x, mut y) = (0i32, 0i32);
+    let mut squares = Vec::new();
+    while x.unsigned_abs() as i64 <= last_x && y as i64 <= last_y && map.is_tree(x, y).is_some() {
+        squares.push((x, y));
+        // Starting from the centre of the first square, the line reaches the
+        // next side of a column after (2 * columns + 1) / (2 * right) and of
+        // a row after (2 * rows + 1) / (2 * down) of a step, compared here
+        // without dividing.
+        let to_column = (2 * x.unsigned_abs() as i64 + 1) * down;
+        let to_row = (2 * y as i64 + 1) * right;
+        if right == 0 || to_row <= to_column {
+            y += 1;
+        }
+        if right != 0 && to_column <= to_row {
+            x += slope.right.signum();
+        }
+    }
+    squares
+}
+
+/// Counts the trees on the squares visited going down `slope`.
+pub fn count_trees_on(map: &Map, slope: Slope, mode: Mode) -> u64 {
+    match mode {
+        Mode::Stepped => count_trees(map, slope.right, slope.down),
+        Mode::Swept => visited(map, slope, mode)
+            .iter()
+            .filter(|&&(x, y)| map.is_tree(x, y) == Some(true))
+            .count() as u64,
+    }
+}
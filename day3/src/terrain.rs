@@ -0,0 +1,132 @@
+use crate::slope::{visited, Mode, Slope};
+use crate::Map;
+use common::ParseError;
+use std::str::FromStr;
+
+/// A kind of square on the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Terrain {
+    pub symbol: char,
+    pub name: String,
+    /// The cost of going through a square, or `None` if it can't be passed.
+    pub cost: Option<u64>,
+}
+
+/// The kinds of terrain a map is made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Legend {
+    terrains: Vec<Terrain>,
+}
+
+impl Legend {
+    pub fn new(terrains: Vec<Terrain>) -> Self {
+        Self { terrains }
+    }
+
+    pub fn terrains(&self) -> &[Terrain] {
+        &self.terrains
+    }
+
+    pub fn get(&self, symbol: char) -> Option<&Terrain> {
+        self.terrains.iter().find(|t| t.symbol == symbol)
+    }
+}
+
+impl Default for Legend {
+    /// Open squares that cost nothing and trees that cost 1, as in the
+    /// puzzle.
+    fn default() -> Self {
+        Self::new(vec![
+            Terrain {
+                symbol: '.',
+                name: "open".to_string(),
+                cost: Some(0),
+            },
+            Terrain {
+                symbol: '#',
+                name: "tree".to_string(),
+                cost: Some(1),
+            },
+        ])
+    }
+}
+
+impl FromStr for Legend {
+    type Err = String;
+
+    /// Parses comma separated `symbol=name:cost` entries, where the cost is
+    /// a number or `impassable`, such as
+    /// `.=open:0,#=tree:1,^=rock:5,%=wall:impassable`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut terrains: Vec<Terrain> = Vec::new();
+        for entry in s.split(',') {
+            let invalid = || format!("Invalid legend entry: {}", entry);
+            let (symbol, rest) = entry.trim().split_once('=').ok_or_else(invalid)?;
+            let (name, cost) = rest.split_once(':').ok_or_else(invalid)?;
+            let mut chars = symbol.chars();
+            let symbol = match (chars.next(), chars.next()) {
+                (Some(symbol), None) => symbol,
+                _ => return Err(invalid()),
+            };
+            let cost = match cost {
+                "impassable" => None,
+                cost => Some(cost.parse::<u64>().map_err(|_| invalid())?),
+            };
+            if terrains.iter().any(|t| t.symbol == symbol) {
+                return Err(format!("Symbol given twice in legend: {}", symbol));
+            }
+            terrains.push(Terrain {
+                symbol,
+                name: name.to_string(),
+                cost,
+            });
+        }
+        Ok(Self::new(terrains))
+    }
+}
+
+/// Reads a map made of the terrain in `legend`.
+pub fn read_map_with(input: &str, legend: &Legend) -> Result<Map, ParseError> {
+    let grid = grid::parse_with(input, 1, |c| legend.get(c).map(|_| c))?;
+    Ok(Map::new(grid))
+}
+
+/// What going down a slope costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cost {
+    /// The total cost of the squares passed.
+    pub total: u64,
+    /// The number of squares passed of each terrain, in the order of the
+    /// legend.
+    pub hits: Vec<(char, u64)>,
+    /// The impassable square that stopped the way down, if any. It's counted
+    /// in `hits` but adds nothing to the total.
+    pub blocked_at: Option<(i32, i32)>,
+}
+
+/// Like `count_trees`, but adds up the cost of every square visited going
+/// down `slope`, stopping at the first impassable one.
+pub fn slope_cost(map: &Map, legend: &Legend, slope: Slope, mode: Mode) -> Cost {
+    let mut cost = Cost {
+        total: 0,
+        hits: legend.terrains.iter().map(|t| (t.symbol, 0)).collect(),
+        blocked_at: None,
+    };
+    for (x, y) in visited(map, slope, mode) {
+        let symbol = map.square(x, y).unwrap();
+        // Maps read with another legend may have squares missing from this one
+        let idx = match legend.terrains.iter().position(|t| t.symbol == symbol) {
+            Some(idx) => idx,
+            None => continue,
+        };
+        cost.hits[idx].1 += 1;
+        match legend.terrains[idx].cost {
+            Some(c) => cost.total += c,
+            None => {
+                cost.blocked_at = Some((x, y));
+                break;
+            }
+        }
+    }
+    cost
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+/// What happens past the edges of the map along one axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// There's nothing past the edges.
+    Bounded,
+    /// The map repeats, so going past one edge comes back in at the other.
+    Toroidal,
+    /// The map repeats mirrored, so going past an edge comes back the way
+    /// it came, starting with the square on the edge.
+    Mirrored,
+}
+
+impl WrapMode {
+    /// Returns the position on a map of `size` squares that `pos` is at, or
+    /// `None` if it's past a bounded edge.
+    pub fn apply(self, pos: i64, size: usize) -> Option<usize> {
+        let size = size as i64;
+        match self {
+            _ if size == 0 => None,
+            WrapMode::Bounded if pos < 0 || pos >= size => None,
+            WrapMode::Bounded => Some(pos as usize),
+            WrapMode::Toroidal => Some(pos.rem_euclid(size) as usize),
+            WrapMode::Mirrored => {
+                let pos = pos.rem_euclid(2 * size);
+                Some(if pos < size { pos } else { 2 * size - 1 - pos } as usize)
+            }
+        }
+    }
+}
+
+impl FromStr for WrapMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bounded" => Ok(WrapMode::Bounded),
+            "toroidal" => Ok(WrapMode::Toroidal),
+            "mirrored" => Ok(WrapMode::Mirrored),
+            _ => Err(format!("Invalid wrap mode: {}", s)),
+        }
+    }
+}
+
+/// How a map wraps along each axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wrap {
+    pub x: WrapMode,
+    pub y: WrapMode,
+    /// The most whole steps of the slope to take going down the map, in
+    /// every mode of visiting squares. Courses that wrap vertically never
+    /// reach the bottom, so they need a limit.
+    pub max_steps: Option<usize>,
+}
+
+impl Default for Wrap {
+    /// Repeating to the right and left and ending at the bottom, as in the
+    /// puzzle.
+    fn default() -> Self {
+        Self {
+            x: WrapMode::Toroidal,
+            y: WrapMode::Bounded,
+            max_steps: None,
+        }
+    }
+}
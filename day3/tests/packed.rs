@@ -0,0 +1,50 @@
+use day3::packed::{count_trees_streaming, PackedMap};
+use day3::slope::Slope;
+use day3::{count_trees, read_map};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+const INPUT: &str = include_str!("../input.txt");
+
+#[test]
+fn same_squares_as_map() {
+    // A row of 70 squares needs two words
+    let wide = format!("{}\n{}\n", "#.".repeat(35), ".##".repeat(23) + "#");
+    for input in [EXAMPLE, &wide].iter() {
+        let map = read_map(input).unwrap();
+        let packed = PackedMap::parse(input).unwrap();
+        assert_eq!(
+            (packed.width(), packed.height()),
+            (map.width(), map.height())
+        );
+        for y in -1..=map.height() as i32 {
+            for x in -80..80 {
+                assert_eq!(packed.is_tree(x, y), map.is_tree(x, y));
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_counts() {
+    let map = read_map(INPUT).unwrap();
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2), (-2, 3)];
+    let counts = count_trees_streaming(
+        INPUT.as_bytes(),
+        "input.txt",
+        &slopes
+            .iter()
+            .map(|&(right, down)| Slope::new(right, down))
+            .collect::<Vec<Slope>>(),
+    )
+    .unwrap();
+    let expected = slopes
+        .iter()
+        .map(|&(right, down)| count_trees(&map, right, down))
+        .collect::<Vec<u64>>();
+    assert_eq!(counts, expected);
+
+    let err = count_trees_streaming("#.\n#\n".as_bytes(), "a.txt", &[Slope::new(1, 1)])
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("a.txt:2:1: expected 2 columns, found 1"));
+}
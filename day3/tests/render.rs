@@ -0,0 +1,27 @@
+use day3::read_map;
+use day3::render::render;
+use day3::slope::{visited, Mode, Slope};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn renders_puzzle_example() {
+    let map = read_map(EXAMPLE).unwrap();
+    let path = visited(&map, Slope::new(3, 1), Mode::Stepped);
+    let text = render(&map, &path, false);
+    let lines = text.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "O.##.........##.........##.......");
+    assert_eq!(lines[2], ".#....X..#..#....#..#..#....#..#.");
+    assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+}
+
+#[test]
+fn renders_left_of_the_map() {
+    let map = read_map("#..\n...\n").unwrap();
+    let text = render(&map, &[(0, 0), (-1, 1)], false);
+    // One copy of the map to the left of the original
+    assert_eq!(text, "#..X..\n..O...\n");
+    let coloured = render(&map, &[(0, 0)], true);
+    assert_eq!(coloured, "\x1b[1;31mX\x1b[0m..\n...\n");
+}
@@ -0,0 +1,52 @@
+use day3::route::safest_route;
+use day3::{read_map, Map};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+/// The fewest trees on any route from `(x, y)` down, trying every route.
+fn fewest_trees(map: &Map, x: i32, y: i32) -> u64 {
+    let here = map.is_tree(x, y).unwrap() as u64;
+    if y as usize == map.height() - 1 {
+        return here;
+    }
+    here + (-1..=1)
+        .map(|dx| fewest_trees(map, x + dx, y + 1))
+        .min()
+        .unwrap()
+}
+
+fn trees_on(map: &Map, path: &[(i32, i32)]) -> u64 {
+    path.iter()
+        .filter(|&&(x, y)| map.is_tree(x, y) == Some(true))
+        .count() as u64
+}
+
+#[test]
+fn finds_fewest_trees() {
+    let map = read_map(EXAMPLE).unwrap();
+    for start in [0, 4, -1].iter() {
+        let route = safest_route(&map, Some(*start)).unwrap();
+        assert_eq!(route.trees, fewest_trees(&map, *start, 0));
+        assert_eq!(route.trees, trees_on(&map, &route.path));
+        assert_eq!(route.path[0], (*start, 0));
+        assert_eq!(route.path.len(), map.height());
+        assert!(route
+            .path
+            .windows(2)
+            .all(|pair| pair[1].1 == pair[0].1 + 1 && (pair[1].0 - pair[0].0).abs() <= 1));
+    }
+    let best = (0..map.width() as i32)
+        .map(|x| fewest_trees(&map, x, 0))
+        .min()
+        .unwrap();
+    assert_eq!(safest_route(&map, None).unwrap().trees, best);
+}
+
+#[test]
+fn wraps_around_the_edge() {
+    // The only way past the trees is through the left edge
+    let map = read_map(".##\n##.\n").unwrap();
+    let route = safest_route(&map, Some(0)).unwrap();
+    assert_eq!(route.trees, 0);
+    assert_eq!(route.path, vec![(0, 0), (-1, 1)]);
+}
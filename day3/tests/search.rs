@@ -0,0 +1,26 @@
+use day3::read_map;
+use day3::search::{rank_slopes, top_k};
+use day3::slope::Slope;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn ranks_slopes_with_ties() {
+    let map = read_map(EXAMPLE).unwrap();
+    let ranked = rank_slopes(&map, 2);
+    assert_eq!(ranked.len(), 2 * 5);
+    assert!(ranked.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(ranked.contains(&(Slope::new(1, 1), 2)));
+
+    let top = top_k(&ranked, 2);
+    assert_eq!(
+        top,
+        &[
+            (Slope::new(2, 1), 1),
+            (Slope::new(0, 2), 1),
+            (Slope::new(2, 2), 1)
+        ]
+    );
+    assert_eq!(top_k(&ranked, 100).len(), ranked.len());
+    assert!(top_k(&ranked, 0).is_empty());
+}
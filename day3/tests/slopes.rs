@@ -0,0 +1,68 @@
+use day3::slope::{count_trees_on, visited, Mode, Slope};
+use day3::{count_trees, read_map};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn stepped_matches_count_trees() {
+    let map = read_map(EXAMPLE).unwrap();
+    for &(right, down) in [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2), (1, 3)].iter() {
+        let slope = Slope::new(right, down);
+        assert_eq!(
+            count_trees_on(&map, slope, Mode::Stepped),
+            count_trees(&map, right, down)
+        );
+    }
+    let squares = visited(&map, Slope::new(1, 3), Mode::Stepped);
+    assert_eq!(squares, vec![(0, 0), (1, 3), (2, 6), (3, 9)]);
+}
+
+#[test]
+fn swept_squares() {
+    let map = read_map("....\n....\n....\n....\n").unwrap();
+    // Straight down, and diagonally through the corners
+    assert_eq!(
+        visited(&map, Slope::new(0, 1), Mode::Swept),
+        vec![(0, 0), (0, 1), (0, 2), (0, 3)]
+    );
+    assert_eq!(
+        visited(&map, Slope::new(1, 1), Mode::Swept),
+        vec![(0, 0), (1, 1), (2, 2), (3, 3)]
+    );
+    // From the centre of the first square, a third of a column per row
+    // reaches the corner at (1, 2), and a quarter crosses into the next
+    // column halfway down the third row
+    assert_eq!(
+        visited(&map, Slope::new(1, 3), Mode::Swept),
+        vec![(0, 0), (0, 1), (1, 2), (1, 3)]
+    );
+    assert_eq!(
+        visited(&map, Slope::new(1, 4), Mode::Swept),
+        vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 3)]
+    );
+    assert_eq!(
+        visited(&map, Slope::new(-2, 1), Mode::Swept),
+        vec![
+            (0, 0),
+            (-1, 0),
+            (-1, 1),
+            (-2, 1),
+            (-3, 1),
+            (-3, 2),
+            (-4, 2),
+            (-5, 2),
+            (-5, 3),
+            (-6, 3),
+            (-7, 3)
+        ]
+    );
+}
+
+#[test]
+fn swept_counts_more_trees() {
+    let map = read_map(EXAMPLE).unwrap();
+    let slope = Slope::new(3, 1);
+    assert!(count_trees_on(&map, slope, Mode::Swept) >= count_trees_on(&map, slope, Mode::Stepped));
+    let squares = visited(&map, slope, Mode::Swept);
+    assert!((0..11).all(|row| squares.iter().any(|&(_, y)| y == row)));
+}
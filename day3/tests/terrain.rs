@@ -0,0 +1,42 @@
+use day3::slope::{Mode, Slope};
+use day3::terrain::{read_map_with, slope_cost, Legend};
+use day3::{count_trees, read_map};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn default_legend_counts_trees() {
+    let map = read_map(EXAMPLE).unwrap();
+    let legend = Legend::default();
+    for &(right, down) in [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].iter() {
+        let cost = slope_cost(&map, &legend, Slope::new(right, down), Mode::Stepped);
+        assert_eq!(cost.total, count_trees(&map, right, down));
+        assert_eq!(cost.hits[1], ('#', cost.total));
+        assert_eq!(cost.blocked_at, None);
+    }
+}
+
+#[test]
+fn weighted_terrain() {
+    let legend = ".=open:0,#=tree:1,^=rock:5,~=ice:0,%=wall:impassable"
+        .parse::<Legend>()
+        .unwrap();
+    let map = read_map_with("#...\n.^..\n..~.\n...#\n", &legend).unwrap();
+    let cost = slope_cost(&map, &legend, Slope::new(1, 1), Mode::Stepped);
+    assert_eq!(cost.total, 1 + 5 + 1);
+    assert_eq!(
+        cost.hits,
+        vec![('.', 0), ('#', 2), ('^', 1), ('~', 1), ('%', 0)]
+    );
+
+    let map = read_map_with("#...\n.%..\n..^.\n", &legend).unwrap();
+    let cost = slope_cost(&map, &legend, Slope::new(1, 1), Mode::Stepped);
+    assert_eq!(cost.total, 1);
+    assert_eq!(cost.blocked_at, Some((1, 1)));
+    assert_eq!(cost.hits[4], ('%', 1));
+
+    assert!(read_map_with("#.x.\n", &legend).is_err());
+    assert!("#=tree".parse::<Legend>().is_err());
+    assert!("#=tree:1,#=rock:5".parse::<Legend>().is_err());
+    assert!("ab=tree:1".parse::<Legend>().is_err());
+}
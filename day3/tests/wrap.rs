@@ -0,0 +1,76 @@
+use day3::route::safest_route;
+use day3::slope::{visited, Mode, Slope};
+use day3::wrap::{Wrap, WrapMode};
+use day3::{count_trees, read_map};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn wrap_modes() {
+    assert_eq!(WrapMode::Bounded.apply(-1, 3), None);
+    assert_eq!(WrapMode::Bounded.apply(2, 3), Some(2));
+    assert_eq!(WrapMode::Toroidal.apply(-1, 3), Some(2));
+    assert_eq!(WrapMode::Toroidal.apply(7, 3), Some(1));
+    let mirrored = (-4..8)
+        .map(|pos| WrapMode::Mirrored.apply(pos, 3).unwrap())
+        .collect::<Vec<usize>>();
+    assert_eq!(mirrored, vec![2, 2, 1, 0, 0, 1, 2, 2, 1, 0, 0, 1]);
+}
+
+#[test]
+fn bounded_sideways() {
+    let map = read_map(EXAMPLE).unwrap();
+    let bounded = read_map(EXAMPLE).unwrap().with_wrap(Wrap {
+        x: WrapMode::Bounded,
+        ..Wrap::default()
+    });
+    // Going left stops at once, and going right stops at the right edge
+    assert_eq!(count_trees(&bounded, -1, 1), 0);
+    assert_eq!(visited(&bounded, Slope::new(-1, 1), Mode::Stepped).len(), 1);
+    assert_eq!(visited(&bounded, Slope::new(3, 1), Mode::Stepped).len(), 4);
+    // The default wraps sideways, going left too
+    assert_eq!(visited(&map, Slope::new(-1, 1), Mode::Stepped).len(), 11);
+    assert_eq!(count_trees(&map, -1, 1), 5);
+
+    let route = safest_route(&bounded, Some(0)).unwrap();
+    assert!(route.path.iter().all(|&(x, _)| (0..11).contains(&x)));
+    assert!(route.trees >= safest_route(&map, Some(0)).unwrap().trees);
+}
+
+#[test]
+fn cyclic_course() {
+    let map = read_map("#..\n...\n").unwrap().with_wrap(Wrap {
+        x: WrapMode::Toroidal,
+        y: WrapMode::Toroidal,
+        max_steps: Some(11),
+    });
+    // Every 6 steps land on the tree again
+    assert_eq!(count_trees(&map, 1, 1), 2);
+    assert_eq!(visited(&map, Slope::new(1, 1), Mode::Stepped).len(), 12);
+    assert_eq!(visited(&map, Slope::new(1, 1), Mode::Swept).len(), 12);
+    let map = read_map("#..\n...\n").unwrap().with_wrap(Wrap {
+        y: WrapMode::Mirrored,
+        max_steps: Some(4),
+        ..Wrap::default()
+    });
+    // The step limit ends both modes at the same square
+    for &(right, down) in [(1, 1), (3, 1), (-2, 3), (0, 2)].iter() {
+        let slope = Slope::new(right, down);
+        let stepped = visited(&map, slope, Mode::Stepped);
+        let swept = visited(&map, slope, Mode::Swept);
+        assert_eq!(stepped.last(), Some(&(4 * right, 4 * down)));
+        assert_eq!(swept.last(), stepped.last());
+    }
+    // Rows 0, 1, 1, 0, 0 and columns 0, 1, 2, 0, 1
+    assert_eq!(count_trees(&map, 1, 1), 2);
+    assert_eq!(count_trees(&map, 0, -1), 3);
+}
+
+#[test]
+#[should_panic]
+fn vertical_wrap_needs_a_limit() {
+    read_map(EXAMPLE).unwrap().with_wrap(Wrap {
+        y: WrapMode::Toroidal,
+        ..Wrap::default()
+    });
+}
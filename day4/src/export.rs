@@ -0,0 +1,449 @@
+use crate::schema::Schema;
+use crate::{read, Passport, PUZZLE};
+use common::cli::{load_input, set_source, Source};
+use common::{set_verbose, Error, ParseError};
+use std::str::FromStr;
+
+pub const EXPORT_USAGE: &str = "Usage: day4 export <input file|-> [export options]
+
+Writes the passports to stdout as CSV, with one column per key, or as JSON
+lines, with one object per passport.
+
+Export options:
+    --input <text>          Use the given text as input instead of a file
+    --to <csv|json>         Output format (default csv)
+    --only <all|valid|invalid>
+                            Which passports to write (default all)
+    --schema <file>         Judge the passports by the rules in a schema file
+                            instead of the puzzle's rules";
+
+pub const IMPORT_USAGE: &str = "Usage: day4 import <input file|-> [import options]
+
+Reads passports written by day4 export and writes them to stdout in the
+puzzle's format.
+
+Import options:
+    --input <text>          Use the given text as input instead of a file
+    --from <csv|json>       Input format (default csv)";
+
+/// The columns that always come first, in this order. Other keys follow in
+/// alphabetical order.
+pub const COLUMNS: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+/// Which passports to export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    Valid,
+    Invalid,
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Filter::All),
+            "valid" => Ok(Filter::Valid),
+            "invalid" => Ok(Filter::Invalid),
+            _ => Err(format!("Invalid filter: {}", s)),
+        }
+    }
+}
+
+impl Filter {
+    /// Whether to export a passport, judged by the rules of `schema`.
+    pub fn includes(self, passport: &Passport, schema: &Schema) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Valid => schema.is_valid(passport),
+            Filter::Invalid => !schema.is_valid(passport),
+        }
+    }
+}
+
+/// Returns the CSV columns for the passports: `COLUMNS`, then any other keys
+/// in alphabetical order.
+pub fn columns(passports: &[Passport]) -> Vec<String> {
+    let mut extra = passports
+        .iter()
+        .flat_map(|p| p.fields().map(|(key, _)| key))
+        .filter(|key| !COLUMNS.contains(key))
+        .map(|key| key.to_string())
+        .collect::<Vec<String>>();
+    extra.sort();
+    extra.dedup();
+    COLUMNS.iter().map(|c| c.to_string()).chain(extra).collect()
+}
+
+/// Writes a value as a CSV cell, quoted if it's empty so that it isn't read
+/// back as a missing field.
+fn csv_cell(value: Option<&str>) -> String {
+    let value = match value {
+        Some(value) => value,
+        None => return String::new(),
+    };
+    if value.is_empty() || value.contains([',', '"']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes the passports as CSV with a header line. Missing fields are
+/// empty cells, and empty values are written as `""`.
+pub fn to_csv(passports: &[Passport]) -> String {
+    let columns = columns(passports);
+    let mut text = columns.join(",") + "\n";
+    for passport in passports.iter() {
+        let cells = columns
+            .iter()
+            .map(|column| csv_cell(passport.get_value(column).as_deref()))
+            .collect::<Vec<String>>();
+        text += &cells.join(",");
+        text.push('\n');
+    }
+    text
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Writes each passport as a JSON object on its own line, with the fields in
+/// the order they were read.
+pub fn to_json_lines(passports: &[Passport]) -> String {
+    let mut text = String::new();
+    for passport in passports.iter() {
+        let fields = passport
+            .fields()
+            .map(|(key, value)| format!("{}:{}", json_string(key), json_string(value)))
+            .collect::<Vec<String>>();
+        text += &format!("{{{}}}\n", fields.join(","));
+    }
+    text
+}
+
+/// Writes passports in the puzzle's format, separated by blank lines.
+pub fn to_batch(passports: &[Passport]) -> String {
+    let lines = passports
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>();
+    let mut text = lines.join("\n\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// Splits a CSV line into cells, undoing the quoting of `csv_cell`. Empty
+/// cells that weren't quoted are `None`.
+fn csv_cells(line_no: usize, line: &str) -> Result<Vec<Option<String>>, ParseError> {
+    let mut cells = vec![None];
+    let mut quoted = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((col, c)) = chars.next() {
+        let cell = cells.last_mut().unwrap();
+        match (c, quoted) {
+            ('"', false) if cell.is_none() => {
+                *cell = Some(String::new());
+                quoted = true;
+            }
+            ('"', true) if chars.peek().map(|&(_, c)| c) == Some('"') => {
+                cell.get_or_insert_with(String::new).push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            (',', false) => cells.push(None),
+            ('"', false) => {
+                let message = "unexpected '\"' in an unquoted cell";
+                return Err(ParseError::new(line_no, col + 1, line, message));
+            }
+            (c, _) => cell.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quoted {
+        return Err(ParseError::new(
+            line_no,
+            line.len() + 1,
+            line,
+            "unclosed quote",
+        ));
+    }
+    Ok(cells)
+}
+
+/// Reads passports written by `to_csv`. Empty cells are missing fields,
+/// while `""` is an empty value. A passport without any fields is an error,
+/// as it would disappear in the puzzle's format and renumber the passports
+/// after it.
+pub fn from_csv(text: &str) -> Result<Vec<Passport>, ParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let header = match lines.next() {
+        Some((idx, line)) => csv_cells(idx + 1, line)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect::<Vec<String>>(),
+        None => return Ok(Vec::new()),
+    };
+    let mut passports = Vec::new();
+    for (idx, line) in lines {
+        let cells = csv_cells(idx + 1, line)?;
+        if cells.len() != header.len() {
+            let message = format!("expected {} cells, found {}", header.len(), cells.len());
+            return Err(ParseError::new(idx + 1, 1, line, &message));
+        }
+        let mut passport = Passport::new();
+        for (key, value) in header.iter().zip(cells.iter()) {
+            if let Some(value) = value {
+                passport.add_field(key, value);
+            }
+        }
+        if passport.fields().next().is_none() {
+            return Err(ParseError::new(idx + 1, 1, line, NO_FIELDS));
+        }
+        passports.push(passport);
+    }
+    Ok(passports)
+}
+
+const NO_FIELDS: &str = "passport with no fields";
+
+/// A parser for the flat objects of string values written by
+/// `to_json_lines`.
+struct JsonLine<'a> {
+    line_no: usize,
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonLine<'a> {
+    fn error(&self, message: &str) -> ParseError {
+        let column = self.line[..self.pos].chars().count() + 1;
+        ParseError::new(self.line_no, column, self.line, message)
+    }
+
+    fn skip_space(&mut self) {
+        let rest = &self.line[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        self.skip_space();
+        if self.line[self.pos..].starts_with(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        let mut chars = self.line[self.pos..].chars();
+        while let Some(c) = chars.next() {
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escaped = chars
+                        .next()
+                        .ok_or_else(|| self.error("unfinished escape"))?;
+                    self.pos += escaped.len_utf8();
+                    match escaped {
+                        '"' | '\\' | '/' => s.push(escaped),
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'u' => {
+                            let hex = chars.by_ref().take(4).collect::<String>();
+                            let c = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .filter(|_| hex.len() == 4)
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid \\u escape"))?;
+                            self.pos += 4;
+                            s.push(c);
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+        Err(self.error("unclosed string"))
+    }
+
+    fn passport(&mut self) -> Result<Passport, ParseError> {
+        let mut passport = Passport::new();
+        self.expect('{')?;
+        self.skip_space();
+        if self.line[self.pos..].starts_with('}') {
+            return Err(self.error(NO_FIELDS));
+        }
+        loop {
+            let start = self.pos;
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.string()?;
+            if !passport.add_field(&key, &value) {
+                self.pos = start;
+                return Err(self.error(&format!("{} given twice", key)));
+            }
+            self.skip_space();
+            match self.line[self.pos..].chars().next() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+        self.skip_space();
+        if self.pos < self.line.len() {
+            return Err(self.error("unexpected text after the object"));
+        }
+        Ok(passport)
+    }
+}
+
+/// Reads passports written by `to_json_lines`, where every value must be a
+/// string. Like `from_csv`, it refuses passports without fields.
+pub fn from_json_lines(text: &str) -> Result<Vec<Passport>, ParseError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            JsonLine {
+                line_no: idx + 1,
+                line,
+                pos: 0,
+            }
+            .passport()
+        })
+        .collect()
+}
+
+pub struct ExportOptions {
+    pub source: Option<Source>,
+    pub format: Format,
+    pub filter: Filter,
+    pub schema: Option<String>,
+}
+
+pub fn parse_export_options(args: &[String]) -> Result<ExportOptions, String> {
+    let mut options = ExportOptions {
+        source: None,
+        format: Format::Csv,
+        filter: Filter::All,
+        schema: None,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                "--to" => options.format = value.parse()?,
+                "--only" => options.filter = value.parse()?,
+                "--schema" => options.schema = Some(value.clone()),
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+pub fn run_export(options: &ExportOptions) -> Result<(), Error> {
+    set_verbose(false);
+    let schema = match &options.schema {
+        Some(file) => Schema::load(file)?,
+        None => Schema::default(),
+    };
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let passports = read(&input)
+        .map_err(|err| err.in_file(&name))?
+        .into_iter()
+        .filter(|p| options.filter.includes(p, &schema))
+        .collect::<Vec<Passport>>();
+    match options.format {
+        Format::Csv => print!("{}", to_csv(&passports)),
+        Format::Json => print!("{}", to_json_lines(&passports)),
+    }
+    Ok(())
+}
+
+pub struct ImportOptions {
+    pub source: Option<Source>,
+    pub format: Format,
+}
+
+pub fn parse_import_options(args: &[String]) -> Result<ImportOptions, String> {
+    let mut options = ImportOptions {
+        source: None,
+        format: Format::Csv,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                "--from" => options.format = value.parse()?,
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+pub fn run_import(options: &ImportOptions) -> Result<(), Error> {
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let passports = match options.format {
+        Format::Csv => from_csv(&input),
+        Format::Json => from_json_lines(&input),
+    }
+    .map_err(|err| err.in_file(&name))?;
+    print!("{}", to_batch(&passports));
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use crate::{read, Passport, PUZZLE};
+use common::cli::{load_input, set_source, Source};
+use common::{set_verbose, Error};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+pub const FRAUD_USAGE: &str = "Usage: day4 fraud <input file|-> [fraud options]
+
+Compares the passports with each other and lists groups of suspicious ones:
+passports sharing a pid, passports with the same pid but different birth
+years, exact duplicates, and passports that differ in only one field but
+share at least two others.
+
+Fraud options:
+    --input <text>          Use the given text as input instead of a file";
+
+/// Why a group of passports is suspicious.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    SharedPid(String),
+    /// The same pid with these different birth years. A passport without a
+    /// `byr` doesn't conflict with any.
+    ConflictingByr {
+        pid: String,
+        byrs: Vec<String>,
+    },
+    /// Passports with exactly the same fields.
+    Duplicate,
+    /// Passports with the same fields apart from this one, which has a
+    /// different value or is missing from some of them. They share at least
+    /// `MIN_SHARED` other fields.
+    NearDuplicate(String),
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::SharedPid(pid) => write!(f, "shared pid {}", pid),
+            Kind::ConflictingByr { pid, byrs } => {
+                write!(f, "pid {} with byr {}", pid, byrs.join(", "))
+            }
+            Kind::Duplicate => write!(f, "duplicates"),
+            Kind::NearDuplicate(field) => write!(f, "differ only in {}", field),
+        }
+    }
+}
+
+/// Suspicious passports, by their indices in the batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    pub kind: Kind,
+    pub records: Vec<usize>,
+}
+
+/// How many fields near duplicates must have in common, so that passports
+/// with a field or two aren't grouped by the one they don't share.
+pub const MIN_SHARED: usize = 2;
+
+/// The keys and values of a passport, sorted.
+type Fields<'a> = Vec<(&'a str, &'a str)>;
+
+/// The fields of a passport apart from `skip`, sorted so that passports
+/// with the same fields compare equal.
+fn signature<'a>(passport: &'a Passport, skip: Option<&str>) -> Fields<'a> {
+    let mut fields = passport
+        .fields()
+        .filter(|&(key, _)| Some(key) != skip)
+        .collect::<Fields>();
+    fields.sort_unstable();
+    fields
+}
+
+fn pid_clusters(passports: &[Passport]) -> Vec<Cluster> {
+    let mut by_pid: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, passport) in passports.iter().enumerate() {
+        if let Some(pid) = passport.get_value("pid") {
+            by_pid.entry(pid).or_default().push(idx);
+        }
+    }
+    let mut clusters = Vec::new();
+    for (pid, records) in by_pid.into_iter().filter(|(_, r)| r.len() > 1) {
+        let mut byrs = records
+            .iter()
+            .filter_map(|&idx| passports[idx].get_value("byr"))
+            .collect::<Vec<String>>();
+        byrs.sort();
+        byrs.dedup();
+        if byrs.len() > 1 {
+            clusters.push(Cluster {
+                kind: Kind::ConflictingByr {
+                    pid: pid.clone(),
+                    byrs,
+                },
+                records: records.clone(),
+            });
+        }
+        clusters.push(Cluster {
+            kind: Kind::SharedPid(pid),
+            records,
+        });
+    }
+    clusters
+}
+
+fn duplicate_clusters(passports: &[Passport]) -> Vec<Cluster> {
+    let mut exact: BTreeMap<Fields, Vec<usize>> = BTreeMap::new();
+    // Passports that differ at most in `key` have the same fields without it,
+    // including those where only some of them have `key`
+    let mut near: BTreeMap<(&str, Fields), Vec<usize>> = BTreeMap::new();
+    let keys = passports
+        .iter()
+        .flat_map(|p| p.fields().map(|(key, _)| key))
+        .collect::<BTreeSet<&str>>();
+    for (idx, passport) in passports.iter().enumerate() {
+        exact
+            .entry(signature(passport, None))
+            .or_default()
+            .push(idx);
+        for &key in keys.iter() {
+            let without = signature(passport, Some(key));
+            if without.len() >= MIN_SHARED {
+                near.entry((key, without)).or_default().push(idx);
+            }
+        }
+    }
+    let mut clusters = exact
+        .into_iter()
+        .filter(|(_, records)| records.len() > 1)
+        .map(|(_, records)| Cluster {
+            kind: Kind::Duplicate,
+            records,
+        })
+        .collect::<Vec<Cluster>>();
+    for ((key, _), records) in near {
+        let values = records
+            .iter()
+            .map(|&idx| passports[idx].get_value(key))
+            .collect::<Vec<Option<String>>>();
+        if values.iter().any(|value| *value != values[0]) {
+            clusters.push(Cluster {
+                kind: Kind::NearDuplicate(key.to_string()),
+                records,
+            });
+        }
+    }
+    clusters
+}
+
+/// Finds the groups of passports that share a pid, that give the same pid
+/// different birth years, that are duplicates, or that differ in only one
+/// field while sharing at least `MIN_SHARED` others. Clusters are ordered
+/// by kind and then by their passports, compared in turn.
+pub fn find_clusters(passports: &[Passport]) -> Vec<Cluster> {
+    let mut clusters = pid_clusters(passports);
+    clusters.extend(duplicate_clusters(passports));
+    clusters.sort_by(|a, b| {
+        let order = |kind: &Kind| match kind {
+            Kind::ConflictingByr { .. } => 0,
+            Kind::SharedPid(_) => 1,
+            Kind::Duplicate => 2,
+            Kind::NearDuplicate(_) => 3,
+        };
+        (order(&a.kind), &a.records, &a.kind).cmp(&(order(&b.kind), &b.records, &b.kind))
+    });
+    clusters
+}
+
+pub struct FraudOptions {
+    pub source: Option<Source>,
+}
+
+pub fn parse_fraud_options(args: &[String]) -> Result<FraudOptions, String> {
+    let mut options = FraudOptions { source: None };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--input" => set_source(&mut options.source, Source::Text(value.clone()))?,
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        } else {
+            set_source(&mut options.source, Source::from_arg(arg))?;
+        }
+    }
+    Ok(options)
+}
+
+pub fn run_fraud(options: &FraudOptions) -> Result<(), Error> {
+    set_verbose(false);
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let passports = read(&input).map_err(|err| err.in_file(&name))?;
+    let clusters = find_clusters(&passports);
+    println!(
+        "{} passports, {} suspicious groups",
+        passports.len(),
+        clusters.len()
+    );
+    for cluster in clusters.iter() {
+        let records = cluster
+            .records
+            .iter()
+            .map(|&idx| match passports[idx].line() {
+                0 => (idx + 1).to_string(),
+                line => format!("{} (line {})", idx + 1, line),
+            })
+            .collect::<Vec<String>>();
+        println!("{}: passports {}", cluster.kind, records.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use crate::schema::{Rule, Schema};
+use crate::valid::{FieldError, Problem};
+use crate::Passport;
+use common::{Answer, Generated, Rng};
+
+/// One rule of a schema for a generated passport to break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    Missing(String),
+    Invalid(String, Problem),
+}
+
+impl Violation {
+    /// Every rule of `schema` that a passport might break, required fields
+    /// first and then the problems each field's rule can have, in the order
+    /// of the schema. Checks between fields aren't included.
+    pub fn all(schema: &Schema) -> Vec<Violation> {
+        let mut all = schema
+            .fields
+            .iter()
+            .filter(|f| f.required)
+            .map(|f| Violation::Missing(f.name.clone()))
+            .collect::<Vec<Violation>>();
+        for field in schema.fields.iter() {
+            let problems: &[Problem] = match field.rule {
+                Rule::Any => &[],
+                Rule::Int { .. } => &[Problem::Malformed, Problem::OutOfRange],
+                Rule::Regex(_) | Rule::Enum(_) => &[Problem::Malformed],
+                Rule::Units(_) => &[Problem::Malformed, Problem::OutOfRange, Problem::BadUnit],
+            };
+            for &problem in problems.iter() {
+                all.push(Violation::Invalid(field.name.clone(), problem));
+            }
+        }
+        all
+    }
+
+    /// The rule as `FieldError::rule` names it, such as `byr out of range`.
+    pub fn rule(&self) -> String {
+        match self {
+            Violation::Missing(field) => format!("{} missing", field),
+            Violation::Invalid(field, problem) => format!("{} {}", field, problem),
+        }
+    }
+}
+
+/// A generated passport and the verdict the schema should give it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub text: String,
+    /// The one rule the passport breaks, or `None` if it's valid.
+    pub expected: Option<Violation>,
+}
+
+/// Picks a number in `range`, often one of the ends.
+fn edge_number(rng: &mut Rng, low: i64, high: i64) -> i64 {
+    match rng.range(0, 3) {
+        0 => low,
+        1 => high,
+        _ => low + rng.range(0, high.saturating_sub(low).max(0) as u64) as i64,
+    }
+}
+
+/// Writes `n` with leading zeros up to `digits`, if given.
+fn pad(n: i64, digits: Option<usize>) -> String {
+    match digits {
+        Some(digits) => format!("{:0width$}", n, width = digits),
+        None => n.to_string(),
+    }
+}
+
+/// Picks a number just outside `low..=high`.
+fn outside(rng: &mut Rng, low: i64, high: i64) -> i64 {
+    let step = rng.range(1, 50) as i64;
+    if rng.chance(0.5) {
+        low.saturating_sub(step)
+    } else {
+        high.saturating_add(step)
+    }
+}
+
+/// Picks a string matching `pattern`, which may only be made of literal
+/// characters and `[...]` classes, each optionally repeated `{n}` or
+/// `{n,m}` times. Returns `None` for other patterns.
+fn sample_regex(rng: &mut Rng, pattern: &str) -> Option<String> {
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let mut text = String::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let choices = match chars[idx] {
+            '[' => {
+                let end = idx + chars[idx..].iter().position(|&c| c == ']')?;
+                let class = &chars[idx + 1..end];
+                idx = end + 1;
+                let mut choices = Vec::new();
+                let mut i = 0;
+                while i < class.len() {
+                    if i + 2 < class.len() && class[i + 1] == '-' {
+                        choices.extend(class[i]..=class[i + 2]);
+                        i += 3;
+                    } else if class[i] == '^' || class[i] == '\\' {
+                        return None;
+                    } else {
+                        choices.push(class[i]);
+                        i += 1;
+                    }
+                }
+                choices
+            }
+            '\\' if idx + 1 < chars.len() && chars[idx + 1].is_ascii_punctuation() => {
+                idx += 2;
+                vec![chars[idx - 1]]
+            }
+            '\\' | '.' | '(' | ')' | '|' | '*' | '+' | '?' | '^' | '$' | '{' | '}' => return None,
+            c => {
+                idx += 1;
+                vec![c]
+            }
+        };
+        let (min, max) = if chars.get(idx) == Some(&'{') {
+            let end = idx + chars[idx..].iter().position(|&c| c == '}')?;
+            let counts = chars[idx + 1..end].iter().collect::<String>();
+            idx = end + 1;
+            match counts.split_once(',') {
+                Some((min, max)) => (min.parse().ok()?, max.parse().ok()?),
+                None => {
+                    let n = counts.parse().ok()?;
+                    (n, n)
+                }
+            }
+        } else {
+            (1, 1)
+        };
+        if choices.is_empty() || min > max {
+            return None;
+        }
+        for _ in 0..rng.range(min, max) {
+            text.push(*rng.choose(&choices));
+        }
+    }
+    Some(text)
+}
+
+/// Picks a value following `rule`, or a value that probably doesn't.
+fn candidate(rng: &mut Rng, rule: &Rule, problem: Option<Problem>) -> Option<String> {
+    let valid = match rule {
+        Rule::Any => rng.range(10, 350).to_string(),
+        Rule::Int { range, digits } => match problem {
+            Some(Problem::OutOfRange) => pad(outside(rng, *range.start(), *range.end()), *digits),
+            _ => pad(edge_number(rng, *range.start(), *range.end()), *digits),
+        },
+        Rule::Regex(re) => {
+            let pattern = re.as_str();
+            let inner = pattern.strip_prefix("^(?:")?.strip_suffix(")$")?;
+            sample_regex(rng, inner)?
+        }
+        Rule::Enum(values) => rng.choose(values).clone(),
+        Rule::Units(units) => {
+            let (unit, range) = rng.choose(units);
+            let n = match problem {
+                Some(Problem::OutOfRange) => outside(rng, *range.start(), *range.end()),
+                _ => edge_number(rng, *range.start(), *range.end()),
+            };
+            let unit = match problem {
+                Some(Problem::BadUnit) => rng.choose(&["", "mm", "m", "ft", "CM", "inch"]),
+                _ => unit.as_str(),
+            };
+            format!("{}{}", n, unit)
+        }
+    };
+    if problem != Some(Problem::Malformed) {
+        return Some(valid);
+    }
+    let mut chars = valid.chars().collect::<Vec<char>>();
+    let pos = rng.index(chars.len() + 1);
+    match rng.range(0, 4) {
+        0 => chars.insert(pos, 'x'),
+        1 => chars.insert(0, '0'),
+        2 => chars.insert(pos, '.'),
+        3 => chars.retain(|c| !c.is_ascii_digit()),
+        _ if !chars.is_empty() => {
+            chars.remove(pos.min(chars.len() - 1));
+        }
+        _ => chars.push('!'),
+    }
+    Some(chars.into_iter().collect())
+}
+
+/// Picks a value that follows `rule`, or has `problem` with it. Returns
+/// `None` if none was found, such as when the rule can't have the problem.
+fn value(rng: &mut Rng, rule: &Rule, problem: Option<Problem>) -> Option<String> {
+    let wanted = problem.map_or(Ok(()), Err);
+    (0..100)
+        .filter_map(|_| candidate(rng, rule, problem))
+        .find(|value| !value.chars().any(char::is_whitespace) && rule.check(value) == wanted)
+}
+
+/// Generates a passport that breaks `violation` of `schema`, or a valid one.
+/// Optional fields are there half the time, and the fields are shuffled and
+/// split over one to three lines. Returns `None` if no such passport was
+/// found, such as when the rule can't be broken that way.
+pub fn case(rng: &mut Rng, schema: &Schema, violation: Option<Violation>) -> Option<Case> {
+    let expected = violation.iter().map(|v| v.rule()).collect::<Vec<String>>();
+    // Retry when checks between fields fail
+    for _ in 0..100 {
+        let mut fields = Vec::new();
+        for field in schema.fields.iter() {
+            let problem = match &violation {
+                Some(Violation::Missing(name)) if *name == field.name => continue,
+                Some(Violation::Invalid(name, problem)) if *name == field.name => Some(*problem),
+                _ if !field.required && rng.chance(0.5) => continue,
+                _ => None,
+            };
+            fields.push((field.name.as_str(), value(rng, &field.rule, problem)?));
+        }
+        let mut passport = Passport::new();
+        for (key, value) in fields.iter() {
+            passport.add_field(key, value);
+        }
+        let rules = schema
+            .violations(&passport)
+            .iter()
+            .map(FieldError::rule)
+            .collect::<Vec<String>>();
+        if rules != expected {
+            continue;
+        }
+        rng.shuffle(&mut fields);
+        let mut text = String::new();
+        for (idx, (key, value)) in fields.iter().enumerate() {
+            if idx > 0 {
+                text.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            text += &format!("{}:{}", key, value);
+        }
+        return Some(Case {
+            text,
+            expected: violation,
+        });
+    }
+    None
+}
+
+/// Generates a passport that is valid about half the time, and otherwise
+/// breaks a rule of `schema` chosen at random.
+pub fn random_case(rng: &mut Rng, schema: &Schema) -> Case {
+    let violations = Violation::all(schema);
+    for _ in 0..100 {
+        let violation = if rng.chance(0.5) || violations.is_empty() {
+            None
+        } else {
+            Some(rng.choose(&violations).clone())
+        };
+        if let Some(case) = case(rng, schema, violation) {
+            return case;
+        }
+    }
+    panic!("can't generate passports for this schema");
+}
+
+/// Generates `size` passports with `random_case` for the puzzle's rules,
+/// with the answers.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let schema = Schema::puzzle();
+    let cases = (0..size.max(1))
+        .map(|_| random_case(rng, schema))
+        .collect::<Vec<Case>>();
+    let complete = cases
+        .iter()
+        .filter(|c| !matches!(c.expected, Some(Violation::Missing(_))))
+        .count();
+    let valid = cases.iter().filter(|c| c.expected.is_none()).count();
+    let texts = cases.into_iter().map(|c| c.text).collect::<Vec<String>>();
+    Generated {
+        input: texts.join("\n\n") + "\n",
+        part1: Some(Answer::from(complete as u64)),
+        part2: Some(Answer::from(valid as u64)),
+    }
+}
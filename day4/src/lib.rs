@@ -0,0 +1,163 @@
+use common::{verbose, Answer, Generator, ParseError, Puzzle};
+use schema::Schema;
+use std::fmt;
+
+pub mod export;
+pub mod fraud;
+pub mod generate;
+pub mod report;
+pub mod run;
+pub mod schema;
+pub mod valid;
+
+/// One passport's `key:value` fields, in the order they were read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Passport {
+    pairs: Vec<(String, String)>,
+    line: usize,
+}
+
+impl Passport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The line the passport starts on, or 0 if it wasn't read from a file.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Adds a field, unless the passport already has one with the same key.
+    /// Returns whether it was added.
+    pub fn add_field(&mut self, key: &str, value: &str) -> bool {
+        if self.has_field(key) {
+            return false;
+        }
+        self.pairs.push((key.to_string(), value.to_string()));
+        true
+    }
+
+    pub fn has_field(&self, field: &str) -> bool {
+        let pair = self.pairs.iter().position(|p| p.0 == field);
+        pair.is_some()
+    }
+
+    pub fn get_value(&self, field: &str) -> Option<String> {
+        let pair = self.pairs.iter().position(|p| p.0 == field);
+        pair.map(|pair| self.pairs[pair].1.clone())
+    }
+
+    /// Iterates over the keys and values in the order they were added.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl fmt::Display for Passport {
+    /// Writes the fields on one line, as in the puzzle input.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs = self
+            .fields()
+            .map(|(k, v)| format!("{}:{}", k, v))
+            .collect::<Vec<String>>();
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+/// Reads the passports, which are separated by blank lines. Fields are
+/// separated by spaces or tabs, and a value may contain `:`. Errors name the
+/// passport, counting from 1, as well as the line.
+pub fn read(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let mut passports = Vec::new();
+    let mut passport = Passport::new();
+    // The line each key of the current passport was first seen on
+    let mut key_lines: Vec<(&str, usize)> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.trim().is_empty() {
+            if passport.line > 0 {
+                passports.push(passport);
+                passport = Passport::new();
+                key_lines.clear();
+            }
+            continue;
+        }
+        if passport.line == 0 {
+            passport.line = line_no;
+        }
+        let record = passports.len() + 1;
+        for pair in line.split_whitespace() {
+            match pair.split_once(':') {
+                Some((key, value)) if !key.is_empty() => {
+                    if !passport.add_field(key, value) {
+                        let first = key_lines.iter().find(|(k, _)| *k == key).unwrap().1;
+                        let message = format!(
+                            "passport {} has {} twice, first on line {}",
+                            record, key, first
+                        );
+                        return Err(ParseError::at(line_no, line, pair, &message));
+                    }
+                    key_lines.push((key, line_no));
+                }
+                _ => {
+                    let message = format!(
+                        "expected key:value in passport {}, found '{}'",
+                        record, pair
+                    );
+                    return Err(ParseError::at(line_no, line, pair, &message));
+                }
+            }
+        }
+    }
+    // The last passport may not be followed by a blank line
+    if passport.line > 0 {
+        passports.push(passport);
+    }
+    verbose!("Num passports: {}", passports.len());
+    Ok(passports)
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    solve_part1_with_schema(input, Schema::puzzle())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    solve_part2_with_schema(input, Schema::puzzle())
+}
+
+/// Counts the passports with all the fields `schema` requires.
+pub fn solve_part1_with_schema(input: &str, schema: &Schema) -> Result<Answer, ParseError> {
+    let passports = read(input)?;
+    let num_valid = passports
+        .iter()
+        .filter(|passport| schema.has_required_fields(passport))
+        .count();
+    Ok(num_valid.into())
+}
+
+/// Counts the passports that follow all the rules of `schema`.
+pub fn solve_part2_with_schema(input: &str, schema: &Schema) -> Result<Answer, ParseError> {
+    let passports = read(input)?;
+    let num_valid = passports
+        .iter()
+        .filter(|passport| schema.is_valid(passport))
+        .count();
+    Ok(num_valid.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 4,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 250,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
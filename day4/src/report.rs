@@ -0,0 +1,94 @@
+use crate::schema::Schema;
+use crate::{read, PUZZLE};
+use common::cli::{load_input, set_source, Source};
+use common::{set_verbose, Error};
+use std::collections::HashMap;
+
+pub const REPORT_USAGE: &str = "Usage: day4 report <input file|-> [report options]
+
+Checks every field of every passport and prints how many passports broke
+each rule, most often broken first.
+
+Report options:
+    --input <text>          Use the given text as input instead of a file
+    --list                  Also list the problems with each invalid passport
+    --schema <file>         Check the passports against the rules in a schema
+                            file instead of the puzzle's rules (see
+                            day4/schema.txt for the format)";
+
+pub struct ReportOptions {
+    pub source: Option<Source>,
+    pub list: bool,
+    pub schema: Option<String>,
+}
+
+pub fn parse_report_options(args: &[String]) -> Result<ReportOptions, String> {
+    let mut options = ReportOptions {
+        source: None,
+        list: false,
+        schema: None,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--list" => options.list = true,
+            "--input" | "--schema" => {
+                let value = rest.next().ok_or(format!("Missing value for {}", arg))?;
+                if arg == "--schema" {
+                    options.schema = Some(value.clone());
+                } else {
+                    set_source(&mut options.source, Source::Text(value.clone()))?;
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ => set_source(&mut options.source, Source::from_arg(arg))?,
+        }
+    }
+    Ok(options)
+}
+
+pub fn run_report(options: &ReportOptions) -> Result<(), Error> {
+    set_verbose(false);
+    let schema = match &options.schema {
+        Some(file) => Schema::load(file)?,
+        None => Schema::default(),
+    };
+    let (input, name) = load_input(&PUZZLE, &options.source)?;
+    let passports = read(&input).map_err(|err| err.in_file(&name))?;
+    let mut rules: HashMap<String, usize> = HashMap::new();
+    let mut invalid = 0;
+    for (idx, passport) in passports.iter().enumerate() {
+        let errors = schema.violations(passport);
+        if errors.is_empty() {
+            continue;
+        }
+        invalid += 1;
+        if options.list {
+            let errors = errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>();
+            println!(
+                "Passport {} (line {}): {}",
+                idx + 1,
+                passport.line(),
+                errors.join(", ")
+            );
+        }
+        for error in errors.iter() {
+            *rules.entry(error.rule()).or_insert(0) += 1;
+        }
+    }
+    println!(
+        "{} passports, {} valid, {} invalid",
+        passports.len(),
+        passports.len() - invalid,
+        invalid
+    );
+    let mut rules = rules.into_iter().collect::<Vec<(String, usize)>>();
+    rules.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    for (rule, count) in rules.iter() {
+        println!("{:>6}  {}", count, rule);
+    }
+    Ok(())
+}
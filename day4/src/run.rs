@@ -0,0 +1,67 @@
+use crate::schema::Schema;
+use crate::{solve_part1_with_schema, solve_part2_with_schema, PUZZLE};
+use common::cli::{
+    parse_options, print_input, print_solutions, solve_puzzle_with, Format, Options, Solution,
+};
+use common::{set_verbose, Error};
+
+pub const RUN_USAGE: &str = "Usage: day4 <input file|-> [options]
+
+The input file can be - to read the input from stdin.
+
+Options:
+    --input <text>          Use the given text as input instead of a file
+    --part <1|2>            Only run one of the parts
+    --format <text|json>    Print answers as text (default) or JSON lines
+    --schema <file>         Solve with the rules in a schema file instead of
+                            the puzzle's rules (see day4/schema.txt for the
+                            format)";
+
+/// The options shared by the day binaries, and the schema to solve with.
+pub struct RunOptions {
+    pub options: Options,
+    pub schema: Option<String>,
+}
+
+pub fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut schema = None;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--schema" {
+            let file = args.next().ok_or("Missing value for --schema")?;
+            schema = Some(file.clone());
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    Ok(RunOptions {
+        options: parse_options(&rest)?,
+        schema,
+    })
+}
+
+/// Solves the selected parts with the schema in `options`, or with the
+/// puzzle's rules if none is given, like `common::cli::solve_puzzle`.
+pub fn solve_puzzle(options: &RunOptions) -> Vec<Result<Solution, Error>> {
+    let loaded = match &options.schema {
+        Some(file) => match Schema::load(file) {
+            Ok(schema) => Some(schema),
+            Err(err) => return vec![Err(err)],
+        },
+        None => None,
+    };
+    let schema = loaded.as_ref().unwrap_or_else(|| Schema::puzzle());
+    solve_puzzle_with(
+        &PUZZLE,
+        &options.options,
+        &|input| solve_part1_with_schema(input, schema),
+        &|input| solve_part2_with_schema(input, schema),
+    )
+}
+
+pub fn run_puzzle(options: &RunOptions) -> Result<(), Error> {
+    set_verbose(options.options.format == Format::Text);
+    print_input(&PUZZLE, &options.options);
+    print_solutions(&PUZZLE, &options.options, solve_puzzle(options))
+}
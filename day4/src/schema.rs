@@ -0,0 +1,291 @@
+use crate::valid::{in_range, parse_digits, split_unit, FieldError, Problem};
+use crate::Passport;
+use common::{read_file, Error, ParseError};
+use regex::Regex;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+
+/// The rules of the puzzle as a schema.
+pub const PUZZLE_SCHEMA: &str = include_str!("../schema.txt");
+
+/// What a field's value must look like.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Any,
+    Int {
+        range: RangeInclusive<i64>,
+        digits: Option<usize>,
+    },
+    Regex(Regex),
+    Enum(Vec<String>),
+    /// A number followed by a unit, with a range for each unit.
+    Units(Vec<(String, RangeInclusive<i64>)>),
+}
+
+impl Rule {
+    pub fn check(&self, value: &str) -> Result<(), Problem> {
+        match self {
+            Rule::Any => Ok(()),
+            Rule::Int { range, digits } => {
+                let digits = digits.map_or(1..=18, |n| n..=n);
+                in_range(parse_digits::<i64>(value, digits)?, range.clone()).map(|_| ())
+            }
+            Rule::Regex(re) if re.is_match(value) => Ok(()),
+            Rule::Enum(values) if values.iter().any(|v| v == value) => Ok(()),
+            Rule::Regex(_) | Rule::Enum(_) => Err(Problem::Malformed),
+            Rule::Units(units) => {
+                let (number, unit) = split_unit(value).ok_or(Problem::Malformed)?;
+                let number = parse_digits::<i64>(number, 1..=18)?;
+                match units.iter().find(|(u, _)| u == unit) {
+                    Some((_, range)) => in_range(number, range.clone()).map(|_| ()),
+                    None => Err(Problem::BadUnit),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldRule {
+    pub name: String,
+    pub required: bool,
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+impl Op {
+    fn parse(s: &str) -> Option<Op> {
+        match s {
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            "==" => Some(Op::Eq),
+            "!=" => Some(Op::Ne),
+            ">=" => Some(Op::Ge),
+            ">" => Some(Op::Gt),
+            _ => None,
+        }
+    }
+
+    fn holds(self, left: i64, right: i64) -> bool {
+        match self {
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+            Op::Eq => left == right,
+            Op::Ne => left != right,
+            Op::Ge => left >= right,
+            Op::Gt => left > right,
+        }
+    }
+}
+
+/// A comparison between the numbers in two fields, such as `iyr <= eyr`.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub left: String,
+    pub op: Op,
+    pub right: String,
+    text: String,
+}
+
+/// Rules for passports, read from a schema file.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub fields: Vec<FieldRule>,
+    pub checks: Vec<Check>,
+}
+
+fn parse_range(line_no: usize, line: &str, part: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let range = part
+        .split_once("..=")
+        .and_then(|(min, max)| Some(min.parse::<i64>().ok()?..=max.parse::<i64>().ok()?));
+    range.ok_or_else(|| ParseError::at(line_no, line, part, "expected a range like 1..=10"))
+}
+
+fn parse_rule(line_no: usize, line: &str, words: &[&str]) -> Result<Rule, ParseError> {
+    let error = |part: &str, message: &str| ParseError::at(line_no, line, part, message);
+    let (kind, args) = match words.split_first() {
+        Some((kind, args)) => (*kind, args),
+        None => {
+            return Err(ParseError::new(
+                line_no,
+                line.len() + 1,
+                line,
+                "missing rule",
+            ))
+        }
+    };
+    match (kind, args) {
+        ("any", []) => Ok(Rule::Any),
+        ("int", [range]) => Ok(Rule::Int {
+            range: parse_range(line_no, line, range)?,
+            digits: None,
+        }),
+        ("int", [range, "digits", digits]) => Ok(Rule::Int {
+            range: parse_range(line_no, line, range)?,
+            digits: Some(
+                digits
+                    .parse::<usize>()
+                    .map_err(|_| error(digits, "expected a number of digits"))?,
+            ),
+        }),
+        ("regex", [pattern]) => Regex::new(&format!("^(?:{})$", pattern))
+            .map(Rule::Regex)
+            .map_err(|err| error(pattern, &format!("invalid regex: {}", err))),
+        ("enum", values) if !values.is_empty() => {
+            Ok(Rule::Enum(values.iter().map(|v| v.to_string()).collect()))
+        }
+        ("units", units) if !units.is_empty() => units
+            .iter()
+            .map(|unit| match unit.split_once(':') {
+                Some((name, range)) => Ok((name.to_string(), parse_range(line_no, line, range)?)),
+                None => Err(error(unit, "expected unit:min..=max")),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Rule::Units),
+        ("any", _) | ("int", _) | ("regex", _) | ("enum", _) | ("units", _) => {
+            Err(error(kind, &format!("wrong arguments for {}", kind)))
+        }
+        _ => Err(error(kind, &format!("unknown rule '{}'", kind))),
+    }
+}
+
+impl Schema {
+    /// Parses a schema. Lines starting with `#` are comments.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut schema = Schema {
+            fields: Vec::new(),
+            checks: Vec::new(),
+        };
+        let mut checks = Vec::new();
+        for (idx, line) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let words = line.split_whitespace().collect::<Vec<&str>>();
+            match words.as_slice() {
+                [] => {}
+                [first, ..] if first.starts_with('#') => {}
+                [kind @ ("required" | "optional"), name, rule @ ..] => {
+                    if schema.fields.iter().any(|f| f.name == *name) {
+                        let message = format!("field {} given twice", name);
+                        return Err(ParseError::at(line_no, line, name, &message));
+                    }
+                    schema.fields.push(FieldRule {
+                        name: name.to_string(),
+                        required: *kind == "required",
+                        rule: parse_rule(line_no, line, rule)?,
+                    });
+                }
+                ["check", left, op, right] => {
+                    let op = Op::parse(op).ok_or_else(|| {
+                        ParseError::at(line_no, line, op, "expected <, <=, ==, !=, >= or >")
+                    })?;
+                    checks.push((line_no, line, [*left, *right]));
+                    schema.checks.push(Check {
+                        left: left.to_string(),
+                        op,
+                        right: right.to_string(),
+                        text: words[1..].join(" "),
+                    });
+                }
+                [first, ..] => {
+                    let message = "expected required, optional or check";
+                    return Err(ParseError::at(line_no, line, first, message));
+                }
+            }
+        }
+        // Fields may be defined after the checks that use them
+        for (line_no, line, names) in checks {
+            for name in names.iter() {
+                if !schema.fields.iter().any(|f| f.name == *name) {
+                    let message = format!("check on undefined field {}", name);
+                    return Err(ParseError::at(line_no, line, name, &message));
+                }
+            }
+        }
+        Ok(schema)
+    }
+
+    /// The rules of the puzzle, parsed once.
+    pub fn puzzle() -> &'static Schema {
+        static PUZZLE: OnceLock<Schema> = OnceLock::new();
+        PUZZLE.get_or_init(|| Self::parse(PUZZLE_SCHEMA).unwrap())
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldRule> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Whether a passport has every required field, whatever their values.
+    pub fn has_required_fields(&self, passport: &Passport) -> bool {
+        self.fields
+            .iter()
+            .all(|f| !f.required || passport.has_field(&f.name))
+    }
+
+    pub fn load(file: &str) -> Result<Self, Error> {
+        Ok(Self::parse(&read_file(file)?).map_err(|err| err.in_file(file))?)
+    }
+
+    /// Checks a passport against the schema, returning every problem found:
+    /// first those with single fields in the order of the schema, then the
+    /// broken checks. Checks involving a field that is missing, invalid or
+    /// not a number are skipped, as there's nothing to compare.
+    pub fn violations(&self, passport: &Passport) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        for field in self.fields.iter() {
+            match passport.get_value(&field.name) {
+                None if field.required => errors.push(FieldError::Missing(field.name.clone())),
+                None => {}
+                Some(value) => {
+                    if let Err(problem) = field.rule.check(&value) {
+                        errors.push(FieldError::Invalid {
+                            field: field.name.clone(),
+                            value,
+                            problem,
+                        });
+                    }
+                }
+            }
+        }
+        let number = |name: &str| {
+            let broken = errors.iter().any(|e| match e {
+                FieldError::Invalid { field, .. } => field == name,
+                _ => false,
+            });
+            if broken {
+                None
+            } else {
+                passport.get_value(name)?.parse::<i64>().ok()
+            }
+        };
+        let broken_checks = self
+            .checks
+            .iter()
+            .filter(|check| match (number(&check.left), number(&check.right)) {
+                (Some(left), Some(right)) => !check.op.holds(left, right),
+                _ => false,
+            })
+            .map(|check| FieldError::Constraint(check.text.clone()))
+            .collect::<Vec<FieldError>>();
+        errors.extend(broken_checks);
+        errors
+    }
+
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        self.violations(passport).is_empty()
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self::puzzle().clone()
+    }
+}
@@ -0,0 +1,251 @@
+use crate::Passport;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// What's wrong with a field's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Problem {
+    /// The value isn't written the way the field is.
+    Malformed,
+    /// A number outside the allowed range.
+    OutOfRange,
+    /// A height without `cm` or `in`.
+    BadUnit,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Malformed => write!(f, "malformed"),
+            Problem::OutOfRange => write!(f, "out of range"),
+            Problem::BadUnit => write!(f, "bad unit"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Height {
+    Cm(u32),
+    In(u32),
+}
+
+impl FromStr for Height {
+    type Err = Problem;
+
+    /// Parses a height of 150 to 193 cm or 59 to 76 in, such as `183cm`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, unit) = split_unit(s).ok_or(Problem::Malformed)?;
+        // As many digits as a schema allows, so `1833cm` is out of range too
+        let number = parse_digits::<u64>(number, 1..=18)?;
+        match unit {
+            "cm" => in_range(number, 150..=193).map(|n| Height::Cm(n as u32)),
+            "in" => in_range(number, 59..=76).map(|n| Height::In(n as u32)),
+            _ => Err(Problem::BadUnit),
+        }
+    }
+}
+
+/// Splits a number with a unit, such as `183cm`, into the digits and the
+/// letters of the unit, which may be empty.
+pub(crate) fn split_unit(s: &str) -> Option<(&str, &str)> {
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(digits);
+    if !number.is_empty() && unit.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some((number, unit))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EyeColor {
+    Amber,
+    Blue,
+    Brown,
+    Gray,
+    Green,
+    Hazel,
+    Other,
+}
+
+impl FromStr for EyeColor {
+    type Err = Problem;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "amb" => Ok(EyeColor::Amber),
+            "blu" => Ok(EyeColor::Blue),
+            "brn" => Ok(EyeColor::Brown),
+            "gry" => Ok(EyeColor::Gray),
+            "grn" => Ok(EyeColor::Green),
+            "hzl" => Ok(EyeColor::Hazel),
+            "oth" => Ok(EyeColor::Other),
+            _ => Err(Problem::Malformed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HairColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl FromStr for HairColor {
+    type Err = Problem;
+
+    /// Parses a colour written `#` and six hex digits, in either case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').ok_or(Problem::Malformed)?;
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Problem::Malformed);
+        }
+        let byte =
+            |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).map_err(|_| Problem::Malformed);
+        Ok(HairColor {
+            r: byte(0)?,
+            g: byte(2)?,
+            b: byte(4)?,
+        })
+    }
+}
+
+/// A passport ID of exactly nine digits, which may start with zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PassportId(u32);
+
+impl PassportId {
+    pub fn value(self) -> u32 {
+        self.0
+    }
+}
+
+impl FromStr for PassportId {
+    type Err = Problem;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_digits::<u32>(s, 9..=9).map(PassportId)
+    }
+}
+
+impl fmt::Display for PassportId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:09}", self.0)
+    }
+}
+
+/// Parses a number of only ASCII digits, with a number of digits in
+/// `digits`.
+pub(crate) fn parse_digits<T: FromStr>(
+    s: &str,
+    digits: RangeInclusive<usize>,
+) -> Result<T, Problem> {
+    if digits.contains(&s.len()) && s.chars().all(|c| c.is_ascii_digit()) {
+        s.parse::<T>().map_err(|_| Problem::Malformed)
+    } else {
+        Err(Problem::Malformed)
+    }
+}
+
+pub(crate) fn in_range<T: PartialOrd>(n: T, range: RangeInclusive<T>) -> Result<T, Problem> {
+    if range.contains(&n) {
+        Ok(n)
+    } else {
+        Err(Problem::OutOfRange)
+    }
+}
+
+/// Parses a four digit year in `years`.
+fn parse_year(s: &str, years: RangeInclusive<u16>) -> Result<u16, Problem> {
+    in_range(parse_digits::<u16>(s, 4..=4)?, years)
+}
+
+/// Why a passport isn't valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    Missing(String),
+    Invalid {
+        field: String,
+        value: String,
+        problem: Problem,
+    },
+    /// A rule between fields that was broken, such as `iyr <= eyr`.
+    Constraint(String),
+}
+
+impl FieldError {
+    /// The rule that was broken, such as `byr out of range`, without the
+    /// value.
+    pub fn rule(&self) -> String {
+        match self {
+            FieldError::Missing(field) => format!("{} missing", field),
+            FieldError::Invalid { field, problem, .. } => format!("{} {}", field, problem),
+            FieldError::Constraint(check) => check.clone(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::Missing(_) => write!(f, "{}", self.rule()),
+            FieldError::Invalid { value, .. } => write!(f, "{}: '{}'", self.rule(), value),
+            FieldError::Constraint(check) => write!(f, "not {}", check),
+        }
+    }
+}
+
+/// A passport with all the required fields, each with a valid value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidPassport {
+    pub byr: u16,
+    pub iyr: u16,
+    pub eyr: u16,
+    pub hgt: Height,
+    pub hcl: HairColor,
+    pub ecl: EyeColor,
+    pub pid: PassportId,
+    pub cid: Option<String>,
+}
+
+/// Parses the value of `field` with `parse`.
+fn field<T>(
+    passport: &Passport,
+    field: &'static str,
+    parse: impl Fn(&str) -> Result<T, Problem>,
+) -> Result<T, FieldError> {
+    let value = passport
+        .get_value(field)
+        .ok_or_else(|| FieldError::Missing(field.to_string()))?;
+    parse(&value).map_err(|problem| FieldError::Invalid {
+        field: field.to_string(),
+        value,
+        problem,
+    })
+}
+
+const BYR: RangeInclusive<u16> = 1920..=2002;
+const IYR: RangeInclusive<u16> = 2010..=2020;
+const EYR: RangeInclusive<u16> = 2020..=2030;
+
+impl TryFrom<&Passport> for ValidPassport {
+    type Error = FieldError;
+
+    /// Checks the fields in the order of the puzzle text, returning the
+    /// first problem found.
+    fn try_from(passport: &Passport) -> Result<Self, Self::Error> {
+        Ok(ValidPassport {
+            byr: field(passport, "byr", |s| parse_year(s, BYR))?,
+            iyr: field(passport, "iyr", |s| parse_year(s, IYR))?,
+            eyr: field(passport, "eyr", |s| parse_year(s, EYR))?,
+            hgt: field(passport, "hgt", str::parse)?,
+            hcl: field(passport, "hcl", str::parse)?,
+            ecl: field(passport, "ecl", str::parse)?,
+            pid: field(passport, "pid", str::parse)?,
+            cid: passport.get_value("cid"),
+        })
+    }
+}
@@ -0,0 +1,29 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day4::{solve_part1, solve_part2};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "example.txt",
+        input: include_str!("../example.txt"),
+        part1: Some(Number(2)),
+        part2: Some(Number(2)),
+    },
+    Expected {
+        name: "example2.txt",
+        input: include_str!("../example2.txt"),
+        part1: Some(Number(8)),
+        part2: Some(Number(4)),
+    },
+    Expected {
+        name: "input.txt",
+        input: include_str!("../input.txt"),
+        part1: Some(Number(170)),
+        part2: Some(Number(103)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
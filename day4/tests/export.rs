@@ -0,0 +1,112 @@
+use day4::export::{from_csv, from_json_lines, to_batch, to_csv, to_json_lines, Filter};
+use day4::schema::Schema;
+use day4::{read, Passport};
+
+const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+zzz:\"a,b\" aaa:back\\slash byr:2001";
+
+/// Compares the fields of passports, ignoring their order and lines.
+fn fields(passports: &[Passport]) -> Vec<Vec<(String, String)>> {
+    passports
+        .iter()
+        .map(|p| {
+            let mut fields = p
+                .fields()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<Vec<(String, String)>>();
+            fields.sort();
+            fields
+        })
+        .collect()
+}
+
+#[test]
+fn csv_columns_and_quoting() {
+    let passports = read(INPUT).unwrap();
+    let csv = to_csv(&passports);
+    let lines = csv.lines().collect::<Vec<&str>>();
+    assert_eq!(lines[0], "byr,iyr,eyr,hgt,hcl,ecl,pid,cid,aaa,zzz");
+    assert_eq!(lines[1], "1937,2017,2020,183cm,#fffffd,gry,860033327,147,,");
+    assert_eq!(lines[3], "2001,,,,,,,,back\\slash,\"\"\"a,b\"\"\"");
+}
+
+#[test]
+fn round_trips() {
+    let passports = read(INPUT).unwrap();
+    let csv = from_csv(&to_csv(&passports)).unwrap();
+    assert_eq!(fields(&csv), fields(&passports));
+
+    let json = to_json_lines(&passports);
+    assert!(json.starts_with("{\"ecl\":\"gry\",\"pid\":\"860033327\","));
+    assert!(json.contains("\"zzz\":\"\\\"a,b\\\"\",\"aaa\":\"back\\\\slash\""));
+    let from_json = from_json_lines(&json).unwrap();
+    assert_eq!(fields(&from_json), fields(&passports));
+
+    let batch = read(&to_batch(&from_json)).unwrap();
+    assert_eq!(fields(&batch), fields(&passports));
+
+    // An empty value is kept apart from a missing field
+    let passports = read("byr:1937 cid:\n\nbyr:1938").unwrap();
+    let csv = to_csv(&passports);
+    assert_eq!(
+        csv,
+        "byr,iyr,eyr,hgt,hcl,ecl,pid,cid\n1937,,,,,,,\"\"\n1938,,,,,,,\n"
+    );
+    assert_eq!(fields(&from_csv(&csv).unwrap()), fields(&passports));
+    let json = to_json_lines(&passports);
+    assert_eq!(fields(&from_json_lines(&json).unwrap()), fields(&passports));
+
+    // A record without fields would be lost in the puzzle's format, so it's
+    // refused rather than renumbering the passports after it
+    let err = from_csv("byr,cid\n1937,\n,\n1938,\n").unwrap_err();
+    assert_eq!(
+        (err.line, err.message.as_str()),
+        (3, "passport with no fields")
+    );
+    let err = from_json_lines("{\"byr\":\"1937\"}\n{ }\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+}
+
+#[test]
+fn filters() {
+    let passports = read(INPUT).unwrap();
+    let schema = Schema::default();
+    let valid = passports
+        .iter()
+        .filter(|p| Filter::Valid.includes(p, &schema))
+        .count();
+    let invalid = passports
+        .iter()
+        .filter(|p| Filter::Invalid.includes(p, &schema))
+        .count();
+    assert_eq!((valid, invalid), (1, 2));
+    assert!(passports.iter().all(|p| Filter::All.includes(p, &schema)));
+}
+
+#[test]
+fn import_errors() {
+    let err = from_csv("byr,iyr\n1937\n").unwrap_err();
+    assert_eq!(
+        (err.line, err.message.as_str()),
+        (2, "expected 2 cells, found 1")
+    );
+    let err = from_csv("byr\n\"1937\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 6));
+
+    let err = from_json_lines("{\"byr\":\"1937\"}\n{\"byr\":1937}\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 8));
+    assert_eq!(err.message, "expected '\"'");
+    let err = from_json_lines("{\"byr\":\"1\",\"byr\":\"2\"}").unwrap_err();
+    assert_eq!((err.column, err.message.as_str()), (12, "byr given twice"));
+    assert_eq!(
+        fields(&from_json_lines("{\"a\":\"\\u0041\\/\"}").unwrap()),
+        vec![vec![("a".to_string(), "A/".to_string())]]
+    );
+    let err = from_json_lines("{\"a\\é\":\"b\"}").unwrap_err();
+    assert_eq!((err.column, err.message.as_str()), (6, "invalid escape"));
+}
@@ -0,0 +1,69 @@
+use day4::fraud::{find_clusters, Cluster, Kind};
+use day4::read;
+
+fn clusters(input: &str) -> Vec<Cluster> {
+    find_clusters(&read(input).unwrap())
+}
+
+#[test]
+fn shared_pids() {
+    let found = clusters(
+        "pid:000000001 byr:1937\n\npid:000000002 byr:1937\n\n\
+         pid:000000001 byr:1940 hgt:183cm\n\npid:000000001\n\npid:000000002 byr:1937 ecl:amb",
+    );
+    let pid_clusters = found
+        .iter()
+        .filter(|c| !matches!(c.kind, Kind::NearDuplicate(_)))
+        .cloned()
+        .collect::<Vec<Cluster>>();
+    assert_eq!(
+        pid_clusters,
+        vec![
+            Cluster {
+                kind: Kind::ConflictingByr {
+                    pid: "000000001".to_string(),
+                    byrs: vec!["1937".to_string(), "1940".to_string()],
+                },
+                records: vec![0, 2, 3],
+            },
+            Cluster {
+                kind: Kind::SharedPid("000000001".to_string()),
+                records: vec![0, 2, 3],
+            },
+            Cluster {
+                kind: Kind::SharedPid("000000002".to_string()),
+                records: vec![1, 4],
+            },
+        ]
+    );
+}
+
+#[test]
+fn near_duplicates() {
+    let found = clusters(
+        "byr:1937 hgt:183cm ecl:gry\n\necl:gry byr:1937 hgt:170cm\n\n\
+         byr:1937 ecl:gry\n\nbyr:1937 hgt:183cm ecl:gry\n\nbyr:2000 hgt:150cm ecl:amb",
+    );
+    assert_eq!(
+        found,
+        vec![
+            Cluster {
+                kind: Kind::Duplicate,
+                records: vec![0, 3],
+            },
+            Cluster {
+                kind: Kind::NearDuplicate("hgt".to_string()),
+                records: vec![0, 1, 2, 3],
+            },
+        ]
+    );
+}
+
+#[test]
+fn nothing_suspicious() {
+    assert!(clusters("byr:1937 pid:1\n\nbyr:1940 pid:2\n\nhgt:183cm").is_empty());
+    assert!(clusters("").is_empty());
+    // Sharing nothing but the field they differ in isn't suspicious
+    assert!(clusters("hgt:183cm\n\nhgt:170cm").is_empty());
+    assert!(clusters("byr:1937 hgt:183cm\n\nbyr:1937 hgt:170cm").is_empty());
+}
@@ -0,0 +1,168 @@
+use common::Rng;
+use day4::generate::{case, random_case, Case, Violation};
+use day4::read;
+use day4::schema::Schema;
+use day4::valid::{Problem, ValidPassport};
+use std::convert::TryFrom;
+use std::env;
+use std::panic;
+
+/// Checks a case against the typed validator and the schema.
+fn check_case(case: &Case, schema: &Schema) {
+    let passports = read(&case.text).unwrap();
+    assert_eq!(passports.len(), 1, "{}", case.text);
+    let passport = &passports[0];
+    let expected = case.expected.as_ref().map(Violation::rule);
+    let verdict = ValidPassport::try_from(passport).err().map(|e| e.rule());
+    assert_eq!(verdict, expected, "{}", case.text);
+    let rules = schema
+        .violations(passport)
+        .iter()
+        .map(|e| e.rule())
+        .collect::<Vec<String>>();
+    let expected = expected.into_iter().collect::<Vec<String>>();
+    assert_eq!(rules, expected, "{}", case.text);
+}
+
+#[test]
+fn each_rule_is_broken_alone() {
+    let schema = Schema::default();
+    let mut rng = Rng::new(1);
+    for _ in 0..50 {
+        check_case(&case(&mut rng, &schema, None).unwrap(), &schema);
+        for violation in Violation::all(&schema) {
+            check_case(&case(&mut rng, &schema, Some(violation)).unwrap(), &schema);
+        }
+    }
+}
+
+#[test]
+fn edge_cases() {
+    let schema = Schema::default();
+    let valid = "byr:2002 iyr:2010 eyr:2030 hgt:59in hcl:#000000 ecl:oth pid:000000000";
+    let cases = [
+        (valid.to_string(), None),
+        (valid.replace("hcl:#000000", "hcl:#ABCdef"), None),
+        (
+            valid.replace("hgt:59in", "hgt:59"),
+            Some(Violation::Invalid("hgt".to_string(), Problem::BadUnit)),
+        ),
+        (
+            valid.replace("hcl:#000000", "hcl:#12345g"),
+            Some(Violation::Invalid("hcl".to_string(), Problem::Malformed)),
+        ),
+        (
+            valid.replace("byr:2002", "byr:02002"),
+            Some(Violation::Invalid("byr".to_string(), Problem::Malformed)),
+        ),
+        (
+            valid.replace("byr:2002", "byr:2003"),
+            Some(Violation::Invalid("byr".to_string(), Problem::OutOfRange)),
+        ),
+        (
+            valid.replace(" pid:000000000", ""),
+            Some(Violation::Missing("pid".to_string())),
+        ),
+    ];
+    for (text, expected) in cases.iter() {
+        check_case(
+            &Case {
+                text: text.clone(),
+                expected: expected.clone(),
+            },
+            &schema,
+        );
+    }
+}
+
+#[test]
+fn other_schemas() {
+    let schema = Schema::parse(
+        "required code regex [A-Z]{2}-[0-9]{3,5}
+required len units m:1..=3 ft:3..=10
+required age int 18..=130
+optional pet enum cat dog
+required born int 1990..=2020 digits 4
+required died int 2000..=2030 digits 4
+check born <= died",
+    )
+    .unwrap();
+    let mut rng = Rng::new(3);
+    let violations = Violation::all(&schema);
+    assert_eq!(violations.len(), 5 + 1 + 3 + 2 + 1 + 2 + 2);
+    for violation in violations {
+        let case = case(&mut rng, &schema, Some(violation.clone())).unwrap();
+        let passport = read(&case.text).unwrap().remove(0);
+        let rules = schema
+            .violations(&passport)
+            .iter()
+            .map(|e| e.rule())
+            .collect::<Vec<String>>();
+        assert_eq!(rules, vec![violation.rule()], "{}", case.text);
+    }
+    // Valid passports also pass the check, which only holds some of the time
+    for _ in 0..20 {
+        let case = case(&mut rng, &schema, None).unwrap();
+        assert!(
+            schema.is_valid(&read(&case.text).unwrap()[0]),
+            "{}",
+            case.text
+        );
+    }
+    // Nothing but a missing value breaks a field that can be anything
+    let schema = Schema::parse("required a any").unwrap();
+    assert_eq!(
+        Violation::all(&schema),
+        vec![Violation::Missing("a".to_string())]
+    );
+    let regex = Schema::parse("required a regex (ab)+").unwrap();
+    assert_eq!(case(&mut rng, &regex, None), None);
+}
+
+/// Replaces, inserts or deletes a few characters, favouring those that
+/// matter to the parser.
+fn mutate(rng: &mut Rng, text: &str) -> String {
+    const CHARS: &[char] = &[
+        ':', ' ', '\n', '\r', '\t', '#', '0', '9', 'a', 'z', 'c', 'm', 'i', 'n', '-', '+', 'é',
+        '\u{0}',
+    ];
+    let mut chars = text.chars().collect::<Vec<char>>();
+    for _ in 0..rng.range(1, 8) {
+        let pos = rng.index(chars.len() + 1);
+        match rng.range(0, 2) {
+            0 if pos < chars.len() => chars[pos] = *rng.choose(CHARS),
+            1 if pos < chars.len() => {
+                chars.remove(pos);
+            }
+            _ => chars.insert(pos, *rng.choose(CHARS)),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Runs the reader and every validator on mangled passports, failing with
+/// the input if any of them panics. Set DAY4_FUZZ_ITERATIONS to run longer.
+#[test]
+fn validators_never_panic() {
+    let iterations = env::var("DAY4_FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|n| n.parse::<u64>().ok())
+        .unwrap_or(2000);
+    let schema = Schema::default();
+    for seed in 0..iterations {
+        let mut rng = Rng::new(seed);
+        let cases = (0..rng.range(1, 3))
+            .map(|_| random_case(&mut rng, &schema).text)
+            .collect::<Vec<String>>();
+        let input = mutate(&mut rng, &cases.join("\n\n"));
+        let result = panic::catch_unwind(|| {
+            if let Ok(passports) = read(&input) {
+                for passport in passports.iter() {
+                    let _ = ValidPassport::try_from(passport);
+                    let _ = schema.violations(passport);
+                }
+            }
+        });
+        assert!(result.is_ok(), "seed {} panicked on {:?}", seed, input);
+    }
+}
@@ -0,0 +1,35 @@
+use day4::read;
+
+#[test]
+fn trailing_record_and_blank_lines() {
+    let passports = read("byr:1937 iyr:2017\n\n\n\nhgt:183cm\necl:gry").unwrap();
+    assert_eq!(passports.len(), 2);
+    assert_eq!(passports[1].get_value("ecl"), Some("gry".to_string()));
+    assert_eq!((passports[0].line(), passports[1].line()), (1, 5));
+    assert!(read("").unwrap().is_empty());
+    assert!(read("\n  \n").unwrap().is_empty());
+}
+
+#[test]
+fn crlf_tabs_and_colons() {
+    let passports = read("byr:1937\tiyr:2017\r\ncid:a:b  \r\n \t\r\nhgt:183cm\r\n").unwrap();
+    assert_eq!(passports.len(), 2);
+    assert_eq!(passports[0].get_value("iyr"), Some("2017".to_string()));
+    assert_eq!(passports[0].get_value("cid"), Some("a:b".to_string()));
+    assert_eq!(passports[1].get_value("hgt"), Some("183cm".to_string()));
+}
+
+#[test]
+fn diagnostics() {
+    let err = read("byr:1937\n\niyr:2017 hgt:183cm\necl:gry iyr:2018\n").unwrap_err();
+    assert_eq!((err.line, err.column), (4, 9));
+    assert_eq!(err.message, "passport 2 has iyr twice, first on line 3");
+
+    let err = read("byr:1937\n\n\nbyr:1937 iyr2017\n").unwrap_err();
+    assert_eq!((err.line, err.column), (4, 10));
+    assert_eq!(
+        err.message,
+        "expected key:value in passport 2, found 'iyr2017'"
+    );
+    assert!(read(":2017\n").is_err());
+}
@@ -0,0 +1,45 @@
+use common::Answer;
+use day4::run::{parse_run_options, solve_puzzle};
+use std::env;
+use std::fs;
+
+fn answers(args: &[&str]) -> Vec<Answer> {
+    let args = args.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+    let options = parse_run_options(&args).unwrap();
+    solve_puzzle(&options)
+        .into_iter()
+        .map(|result| result.unwrap().answer)
+        .collect()
+}
+
+#[test]
+fn custom_schema() {
+    let file = env::temp_dir().join(format!("day4-run-{}.txt", std::process::id()));
+    fs::write(&file, "required byr int 1..=2000\noptional hgt any\n").unwrap();
+    let file = file.to_str().unwrap();
+    let input = "byr:1 hgt:x\n\nhgt:y\n\nbyr:3000";
+    assert_eq!(
+        answers(&["--input", input, "--schema", file]),
+        vec![Answer::Number(2), Answer::Number(1)]
+    );
+    assert_eq!(
+        answers(&["--schema", file, "--part", "2", "--input", input]),
+        vec![Answer::Number(1)]
+    );
+    assert_eq!(
+        answers(&["--input", input]),
+        vec![Answer::Number(0), Answer::Number(0)]
+    );
+
+    let missing = ["--input", input, "--schema", "no/such/schema.txt"];
+    let options = parse_run_options(&missing.iter().map(|a| a.to_string()).collect::<Vec<_>>());
+    assert!(solve_puzzle(&options.unwrap())[0].is_err());
+    let _ = fs::remove_file(file);
+}
+
+#[test]
+fn option_errors() {
+    let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+    assert!(parse_run_options(&args(&["--schema"])).is_err());
+    assert!(parse_run_options(&args(&["--rules", "x"])).is_err());
+}
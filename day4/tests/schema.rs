@@ -0,0 +1,123 @@
+use common::Answer::Number;
+use day4::schema::Schema;
+use day4::valid::{FieldError, Problem, ValidPassport};
+use day4::{read, solve_part1_with_schema, solve_part2_with_schema};
+use std::convert::TryFrom;
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn passport(text: &str) -> day4::Passport {
+    read(&format!("{}\n\n", text)).unwrap().remove(0)
+}
+
+#[test]
+fn puzzle_schema_matches_puzzle_rules() {
+    let schema = Schema::puzzle();
+    for passport in read(INPUT).unwrap().iter() {
+        let first = schema.violations(passport).into_iter().next();
+        assert_eq!(first, ValidPassport::try_from(passport).err());
+    }
+}
+
+#[test]
+fn puzzle_violations() {
+    let schema = Schema::puzzle();
+    let rules = |p: &day4::Passport| {
+        schema
+            .violations(p)
+            .iter()
+            .map(|v| v.rule())
+            .collect::<Vec<String>>()
+    };
+    let p = passport("eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926");
+    assert_eq!(
+        rules(&p),
+        vec!["eyr out of range", "hgt bad unit", "pid malformed"]
+    );
+    assert_eq!(schema.violations(&p)[1].to_string(), "hgt bad unit: '170'");
+
+    let p = passport("hcl:dab227 iyr:2012 ecl:brn hgt:182cm pid:021572410 eyr:2020");
+    assert_eq!(rules(&p), vec!["byr missing", "hcl malformed"]);
+
+    let p = passport("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f");
+    assert!(schema.violations(&p).is_empty());
+}
+
+#[test]
+fn custom_rules() {
+    let schema = Schema::parse(
+        "# Looser heights, and no passports from the future
+required iyr int 2010..=2020
+required eyr int 2020..=2030
+required hgt units cm:100..=250 in:40..=100 m:1..=2
+optional ecl enum amb blu
+check iyr <= eyr
+check eyr < iyr",
+    )
+    .unwrap();
+    let p = passport("iyr:2015 eyr:2020 hgt:2m ecl:blu");
+    assert_eq!(
+        schema.violations(&p),
+        vec![FieldError::Constraint("eyr < iyr".to_string())]
+    );
+    let p = passport("iyr:2015 eyr:2020 hgt:3ft ecl:zzz");
+    assert_eq!(
+        schema
+            .violations(&p)
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec![
+            "hgt bad unit: '3ft'",
+            "ecl malformed: 'zzz'",
+            "not eyr < iyr"
+        ]
+    );
+    // Checks on invalid fields are skipped
+    let p = passport("iyr:2015 eyr:2040 hgt:120cm");
+    assert_eq!(
+        schema.violations(&p),
+        vec![FieldError::Invalid {
+            field: "eyr".to_string(),
+            value: "2040".to_string(),
+            problem: Problem::OutOfRange
+        }]
+    );
+    assert!(!schema.is_valid(&passport("eyr:2020")));
+}
+
+#[test]
+fn schema_errors() {
+    let err = Schema::parse("required byr int 1920..2002").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 18));
+    assert_eq!(err.message, "expected a range like 1..=10");
+    let err = Schema::parse("\nrequired hcl regex #[0-9").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 20));
+    assert!(err.message.starts_with("invalid regex"));
+    let err = Schema::parse("check iyr => eyr").unwrap_err();
+    assert_eq!(err.column, 11);
+    assert_eq!(
+        Schema::parse("required a any\noptional a any")
+            .unwrap_err()
+            .message,
+        "field a given twice"
+    );
+    assert_eq!(
+        Schema::parse("required a color").unwrap_err().message,
+        "unknown rule 'color'"
+    );
+    let err = Schema::parse("required a int 1..=9\ncheck a < b").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 11));
+    assert_eq!(err.message, "check on undefined field b");
+    assert!(Schema::parse("check a < b\nrequired a any\noptional b any").is_ok());
+    assert!(Schema::parse("forbidden a any").is_err());
+    assert!(Schema::parse("required a enum").is_err());
+}
+
+#[test]
+fn solving_with_a_schema() {
+    let input = "byr:1937 hgt:183cm\n\nbyr:1990\n\nhgt:2m";
+    let schema = Schema::parse("required byr int 1930..=1950\noptional hgt any").unwrap();
+    assert_eq!(solve_part1_with_schema(input, &schema).unwrap(), Number(2));
+    assert_eq!(solve_part2_with_schema(input, &schema).unwrap(), Number(1));
+}
@@ -0,0 +1,63 @@
+use day4::read;
+use day4::valid::{EyeColor, FieldError, HairColor, Height, PassportId, Problem, ValidPassport};
+use std::convert::TryFrom;
+
+fn passport(text: &str) -> day4::Passport {
+    read(&format!("{}\n\n", text)).unwrap().remove(0)
+}
+
+#[test]
+fn typed_fields() {
+    let p = passport("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f");
+    let valid = ValidPassport::try_from(&p).unwrap();
+    assert_eq!(valid.byr, 1980);
+    assert_eq!(valid.hgt, Height::In(74));
+    assert_eq!(valid.ecl, EyeColor::Green);
+    assert_eq!(
+        valid.hcl,
+        HairColor {
+            r: 0x62,
+            g: 0x3a,
+            b: 0x2f
+        }
+    );
+    assert_eq!(valid.pid.to_string(), "087499704");
+    assert_eq!(valid.pid, "087499704".parse::<PassportId>().unwrap());
+    assert_eq!(valid.cid, None);
+}
+
+#[test]
+fn invalid_values() {
+    assert_eq!("190cm".parse::<Height>(), Ok(Height::Cm(190)));
+    assert!("190in".parse::<Height>().is_err());
+    assert!("190".parse::<Height>().is_err());
+    assert!("+60in".parse::<Height>().is_err());
+    assert_eq!(
+        "#ABcd0F".parse::<HairColor>(),
+        Ok(HairColor {
+            r: 0xab,
+            g: 0xcd,
+            b: 0x0f
+        })
+    );
+    assert!("#123abz".parse::<HairColor>().is_err());
+    assert!("123abc".parse::<HairColor>().is_err());
+    assert!("wat".parse::<EyeColor>().is_err());
+    assert!("0123456789".parse::<PassportId>().is_err());
+
+    let p = passport("eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926");
+    assert_eq!(
+        ValidPassport::try_from(&p),
+        Err(FieldError::Invalid {
+            field: "eyr".to_string(),
+            value: "1972".to_string(),
+            problem: Problem::OutOfRange,
+        })
+    );
+    // Missing fields are errors, not panics
+    let p = passport("iyr:2019 hcl:#602927 eyr:1967 hgt:170cm");
+    assert_eq!(
+        ValidPassport::try_from(&p),
+        Err(FieldError::Missing("byr".to_string()))
+    );
+}
@@ -0,0 +1,25 @@
+use crate::seat::SeatCode;
+use common::{Answer, Generated, Rng};
+
+/// Generates boarding passes for `size` consecutive seats (at most 1000) with
+/// one missing in the middle, in random order. Both answers are known.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(3) as u64;
+    let first = rng.range(1, 1023 - size);
+    let last = first + size;
+    let missing = rng.range(first + 1, last - 1);
+    let mut ids = (first..=last)
+        .filter(|id| *id != missing)
+        .collect::<Vec<u64>>();
+    rng.shuffle(&mut ids);
+    let code = SeatCode::default();
+    let input = ids
+        .iter()
+        .map(|&id| code.encode(id as u32).unwrap() + "\n")
+        .collect();
+    Generated {
+        input,
+        part1: Some(Answer::from(last)),
+        part2: Some(Answer::from(missing)),
+    }
+}
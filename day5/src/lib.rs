@@ -0,0 +1,62 @@
+use common::{Answer, Generator, ParseError, Puzzle};
+
+mod generate;
+pub mod seat;
+
+use seat::{Seat, SeatCode};
+
+fn read(input: &str) -> Result<Vec<Seat>, ParseError> {
+    let code = SeatCode::default();
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| code.parse(idx + 1, line))
+        .collect()
+}
+
+fn get_highest_seat(seats: &[Seat]) -> u32 {
+    seats
+        .iter()
+        .map(|seat| seat.id)
+        .fold(0, |sofar, id| sofar.max(id))
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    Ok(get_highest_seat(&read(input)?).into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let seat_codes = read(input)?;
+    let highest = get_highest_seat(&seat_codes);
+    let mut seats = vec![false; highest as usize + 1];
+    seat_codes.iter().map(|seat| seat.id).for_each(|id| {
+        seats[id as usize] = true;
+    });
+    let mut seat = Answer::NotFound;
+    for idx in 1..seats.len() - 1 {
+        let prev = seats[idx - 1];
+        let next = seats[idx + 1];
+        let current = seats[idx];
+        if prev && next && !current {
+            seat = idx.into();
+        }
+    }
+    Ok(seat)
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 5,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 900,
+        max_size: Some(1000),
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,119 @@
+use common::ParseError;
+
+/// A seat's row and column, and its ID with the row's bits above the
+/// column's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u32,
+    pub col: u32,
+    pub id: u32,
+}
+
+/// How boarding passes are written for a plane: a letter for each bit of
+/// the row, highest bit first, then a letter for each bit of the column.
+/// Each pair of letters gives the letter for the lower half first, like
+/// `F` and `B`. The default is the puzzle's plane, with 128 rows of 8 seats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatCode {
+    row_bits: u32,
+    col_bits: u32,
+    row_letters: [char; 2],
+    col_letters: [char; 2],
+}
+
+impl SeatCode {
+    pub fn new(
+        row_bits: u32,
+        col_bits: u32,
+        row_letters: [char; 2],
+        col_letters: [char; 2],
+    ) -> Self {
+        assert!(row_bits + col_bits <= 32, "seat IDs must fit in 32 bits");
+        assert!(
+            row_letters[0] != row_letters[1] && col_letters[0] != col_letters[1],
+            "the two halves need different letters"
+        );
+        Self {
+            row_bits,
+            col_bits,
+            row_letters,
+            col_letters,
+        }
+    }
+
+    /// The number of letters in a code.
+    pub fn len(&self) -> usize {
+        (self.row_bits + self.col_bits) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of seat IDs, one more than the highest.
+    pub fn seats(&self) -> u64 {
+        1 << (self.row_bits + self.col_bits)
+    }
+
+    /// The letters allowed at `idx` in a code.
+    fn letters(&self, idx: usize) -> [char; 2] {
+        if idx < self.row_bits as usize {
+            self.row_letters
+        } else {
+            self.col_letters
+        }
+    }
+
+    /// Decodes a code like `FBFBBFFRLR`, or returns `None` if it has the
+    /// wrong length or letters.
+    pub fn decode(&self, code: &str) -> Option<Seat> {
+        self.parse(1, code).ok()
+    }
+
+    /// Like `decode`, but says what's wrong with line `line_no` of an input.
+    pub fn parse(&self, line_no: usize, line: &str) -> Result<Seat, ParseError> {
+        let mut id = 0u64;
+        let mut len = 0;
+        for (idx, c) in line.chars().enumerate() {
+            let bit = match self.letters(idx).iter().position(|&l| l == c) {
+                Some(bit) if idx < self.len() => bit,
+                _ => {
+                    let message = format!("unexpected character '{}'", c);
+                    return Err(ParseError::new(line_no, idx + 1, line, &message));
+                }
+            };
+            id = id << 1 | bit as u64;
+            len += 1;
+        }
+        if len < self.len() {
+            let message = format!("expected {} characters, found {}", self.len(), len);
+            return Err(ParseError::new(line_no, len + 1, line, &message));
+        }
+        Ok(Seat {
+            row: (id >> self.col_bits) as u32,
+            col: (id & ((1 << self.col_bits) - 1)) as u32,
+            id: id as u32,
+        })
+    }
+
+    /// Writes seat `id` as a code, or returns `None` if the plane has no such
+    /// seat.
+    pub fn encode(&self, id: u32) -> Option<String> {
+        if id as u64 >= self.seats() {
+            return None;
+        }
+        let code = (0..self.len())
+            .map(|idx| {
+                let bit = id as u64 >> (self.len() - 1 - idx) & 1;
+                self.letters(idx)[bit as usize]
+            })
+            .collect();
+        Some(code)
+    }
+}
+
+impl Default for SeatCode {
+    fn default() -> Self {
+        SeatCode::new(7, 3, ['F', 'B'], ['L', 'R'])
+    }
+}
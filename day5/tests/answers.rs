@@ -0,0 +1,23 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::{NotFound, Number};
+use day5::{solve_part1, solve_part2};
+
+const EXPECTED: &[Expected] = &[
+    Expected {
+        name: "example.txt",
+        input: include_str!("../example.txt"),
+        part1: Some(Number(820)),
+        part2: Some(NotFound),
+    },
+    Expected {
+        name: "input.txt",
+        input: include_str!("../input.txt"),
+        part1: Some(Number(953)),
+        part2: Some(Number(615)),
+    },
+];
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
@@ -0,0 +1,69 @@
+use day5::seat::{Seat, SeatCode};
+
+#[test]
+fn puzzle_plane() {
+    let code = SeatCode::default();
+    let cases = [
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+        ("FFFFFFFLLL", 0, 0, 0),
+        ("BBBBBBBRRR", 127, 7, 1023),
+    ];
+    for &(text, row, col, id) in cases.iter() {
+        assert_eq!(code.decode(text), Some(Seat { row, col, id }), "{}", text);
+        assert_eq!(code.encode(id).as_deref(), Some(text));
+    }
+    assert_eq!(code.encode(1024), None);
+    assert_eq!(code.decode("FBFBBFFRL"), None);
+    assert_eq!(code.decode("FBFBBFFRLRR"), None);
+    assert_eq!(code.decode("FBFBBFBRLR").map(|s| s.id), Some(365));
+    assert_eq!(code.decode("FBFBBFRRLR"), None);
+}
+
+#[test]
+fn other_planes() {
+    // 16 rows of 4 seats, rows written U/D and seats A/B
+    let code = SeatCode::new(4, 2, ['U', 'D'], ['A', 'B']);
+    assert_eq!((code.len(), code.seats()), (6, 64));
+    assert_eq!(
+        code.decode("DUUDBA"),
+        Some(Seat {
+            row: 9,
+            col: 2,
+            id: 38
+        })
+    );
+    for id in 0..64 {
+        let text = code.encode(id).unwrap();
+        assert_eq!(code.decode(&text).map(|s| s.id), Some(id));
+    }
+    assert_eq!(code.encode(64), None);
+
+    // A single row, so codes are only column letters
+    let code = SeatCode::new(0, 3, ['F', 'B'], ['L', 'R']);
+    assert_eq!(
+        code.decode("RLR"),
+        Some(Seat {
+            row: 0,
+            col: 5,
+            id: 5
+        })
+    );
+}
+
+#[test]
+fn diagnostics() {
+    let code = SeatCode::default();
+    let err = code.parse(3, "FBFBBFLRLR").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 7));
+    assert_eq!(err.message, "unexpected character 'L'");
+    let err = code.parse(1, "FBFBBFFRLRL").unwrap_err();
+    assert_eq!(err.column, 11);
+    let err = code.parse(1, "FBFB").unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (5, "expected 10 characters, found 4")
+    );
+}
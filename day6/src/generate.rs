@@ -0,0 +1,22 @@
+use common::{Generated, Rng};
+
+/// Generates `size` groups of one to five people, each answering yes to a
+/// random set of questions.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut groups = Vec::new();
+    for _ in 0..size.max(1) {
+        // Groups share some of their answers, like in the real input
+        let common = ('a'..='z').filter(|_| rng.chance(0.3)).collect::<String>();
+        let mut people = Vec::new();
+        for _ in 0..rng.range(1, 5) {
+            let mut answers = common.clone();
+            answers.extend(('a'..='z').filter(|c| !common.contains(*c) && rng.chance(0.2)));
+            if answers.is_empty() {
+                answers.push((b'a' + rng.range(0, 25) as u8) as char);
+            }
+            people.push(answers);
+        }
+        groups.push(people.join("\n"));
+    }
+    Generated::new(groups.join("\n\n") + "\n")
+}
@@ -0,0 +1,104 @@
+use common::{Answer, Generator, ParseError, Puzzle};
+use std::collections::HashMap;
+
+mod generate;
+
+fn read(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut lines = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        if let Some((col, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            let message = format!("unexpected character '{}'", c);
+            return Err(ParseError::new(idx + 1, col + 1, line, &message));
+        }
+        lines.push(line.chars().collect());
+    }
+    Ok(lines)
+}
+
+struct Answers {
+    map: HashMap<char, u32>,
+    num_people: u32,
+}
+
+impl Answers {
+    fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            num_people: 0,
+        }
+    }
+
+    fn add_answers(&mut self, new_answers: &[char]) {
+        new_answers
+            .iter()
+            .for_each(|yes| *self.map.entry(*yes).or_insert(0) += 1);
+        self.num_people += 1;
+    }
+
+    fn get_count(&self) -> usize {
+        self.map.keys().len()
+    }
+
+    fn get_all_count(&self) -> usize {
+        let mut sum = 0;
+        for (_, num) in self.map.iter() {
+            if *num == self.num_people {
+                sum += 1;
+            }
+        }
+        sum
+    }
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let lines = read(input)?;
+    let mut sum = 0;
+    let mut answers = Answers::new();
+    for line in lines.iter() {
+        if line.is_empty() {
+            sum += answers.get_count();
+            answers = Answers::new();
+        } else {
+            answers.add_answers(line);
+        }
+    }
+    sum += answers.get_count();
+    Ok(sum.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let lines = read(input)?;
+    let mut sum = 0;
+    let mut answers = Answers::new();
+    for line in lines.iter() {
+        if line.is_empty() {
+            sum += answers.get_all_count();
+            answers = Answers::new();
+        } else {
+            answers.add_answers(line);
+        }
+    }
+    sum += answers.get_all_count();
+    Ok(sum.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 6,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 500,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
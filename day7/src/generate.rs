@@ -0,0 +1,101 @@
+use common::{Generated, Rng};
+
+const ADJECTIVES: &[&str] = &[
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "striped", "vibrant", "wavy",
+];
+const COLORS: &[&str] = &[
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "turquoise",
+    "violet",
+    "white",
+    "yellow",
+];
+const LAYERS: usize = 8;
+const GOLD_LAYER: usize = 5;
+
+fn color_name(idx: usize) -> String {
+    let adjective = ADJECTIVES[idx % ADJECTIVES.len()];
+    let color = COLORS[(idx / ADJECTIVES.len()) % COLORS.len()];
+    let round = idx / (ADJECTIVES.len() * COLORS.len());
+    if round == 0 {
+        format!("{} {}", adjective, color)
+    } else {
+        format!("{}{} {}", adjective, round, color)
+    }
+}
+
+/// Generates rules for about `size` bags. The bags are in layers where each
+/// bag only contains bags from the next layer, which keeps the number of bags
+/// inside shiny gold small. At least one bag contains shiny gold.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(LAYERS);
+    // Neither "shiny" nor "gold" is in the lists, so no name clashes with it
+    let mut names = (0..size).map(color_name).collect::<Vec<String>>();
+    rng.shuffle(&mut names);
+    let layer_size = size / LAYERS;
+    let mut layers = names
+        .chunks(layer_size)
+        .take(LAYERS)
+        .map(|names| names.to_vec())
+        .collect::<Vec<Vec<String>>>();
+    layers[GOLD_LAYER][0] = "shiny gold".to_string();
+
+    let mut rules = Vec::new();
+    for (layer, bags) in layers.iter().enumerate() {
+        for (idx, bag) in bags.iter().enumerate() {
+            let mut contents = Vec::new();
+            if layer + 1 < LAYERS && (bag == "shiny gold" || rng.chance(0.6)) {
+                let next = &layers[layer + 1];
+                let mut inside = (0..rng.range(1, 3))
+                    .map(|_| rng.index(next.len()))
+                    .collect::<Vec<usize>>();
+                inside.sort_unstable();
+                inside.dedup();
+                for idx in inside {
+                    let num = rng.range(1, 5);
+                    let plural = if num == 1 { "" } else { "s" };
+                    contents.push(format!("{} {} bag{}", num, next[idx], plural));
+                }
+            }
+            let has_gold = contents.iter().any(|c| c.contains(" shiny gold bag"));
+            if layer + 1 == GOLD_LAYER && idx == 0 && !has_gold {
+                contents.push("1 shiny gold bag".to_string());
+            }
+            let contents = if contents.is_empty() {
+                "no other bags".to_string()
+            } else {
+                contents.join(", ")
+            };
+            rules.push(format!("{} bags contain {}.\n", bag, contents));
+        }
+    }
+    rng.shuffle(&mut rules);
+    Generated::new(rules.concat())
+}
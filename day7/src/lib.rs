@@ -0,0 +1,157 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+mod generate;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(
+        r#"(?x)
+            ^
+            (?P<color>.+)
+            \sbags\scontain\s
+            (?P<content>.+)
+            \.$
+        "#,
+    )
+    .unwrap();
+    static ref CONTENT_RE: Regex = Regex::new(
+        r#"(?x)
+            ^
+            (?P<num>\d+)
+            \s
+            (?P<color>.+)
+            \sbag
+        "#,
+    )
+    .unwrap();
+}
+
+struct Bag {
+    color: String,
+    bags: Vec<(i32, String)>,
+}
+
+impl Bag {
+    fn new(color: &str) -> Self {
+        Self {
+            color: color.to_string(),
+            bags: Vec::new(),
+        }
+    }
+
+    fn add_bag(&mut self, num: i32, color: &str) {
+        self.bags.push((num, color.to_string()))
+    }
+
+    /*
+    fn print(&self) {
+        let other = self
+            .bags
+            .iter()
+            .map(|b| format!("{}x'{}'", b.0, b.1))
+            .collect::<Vec<String>>()
+            .join(", ");
+        println!("'{}': {}", self.color, other);
+    }
+    */
+}
+
+fn read_bags(input: &str) -> Result<Vec<Bag>, ParseError> {
+    let mut bags = Vec::new();
+    for (idx, l) in input.lines().enumerate() {
+        let caps = LINE_RE.captures(l);
+        if let Some(caps) = caps {
+            let color = &caps["color"];
+            let content = &caps["content"];
+            let mut bag = Bag::new(color);
+            if content != "no other bags" {
+                for other in content.split(", ") {
+                    let caps = CONTENT_RE.captures(other);
+                    if let Some(caps) = caps {
+                        let num = parse_field(idx + 1, l, &caps["num"])?;
+                        let color = &caps["color"];
+                        bag.add_bag(num, color);
+                    } else {
+                        let message = format!("expected '<num> <color> bags', found '{}'", other);
+                        return Err(ParseError::at(idx + 1, l, other, &message));
+                    }
+                }
+            }
+            bags.push(bag);
+        } else {
+            let message = "expected '<color> bags contain <content>.'";
+            return Err(ParseError::new(idx + 1, 1, l, message));
+        }
+    }
+    //for bag in bags.iter() {
+    //    bag.print();
+    //}
+    Ok(bags)
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_bags(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let bags = read_bags(input)?;
+    let mut count = 0;
+    for bag in bags.iter() {
+        if bag.color == "shiny gold" {
+            continue;
+        }
+        let mut bags_to_visit = vec![bag.color.clone()];
+        while let Some(other_color) = bags_to_visit.pop() {
+            let idx = bags.iter().position(|b| b.color == other_color);
+            if let Some(idx) = idx {
+                let other_bag = &bags[idx];
+                bags_to_visit.extend(other_bag.bags.iter().map(|b| b.1.clone()));
+            }
+            if other_color == "shiny gold" {
+                count += 1;
+                break;
+            }
+        }
+    }
+
+    Ok(count.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let bags = read_bags(input)?;
+    let mut count = 0;
+    let bag = match bags.iter().find(|b| b.color == "shiny gold") {
+        Some(bag) => bag,
+        None => return Ok(Answer::NotFound),
+    };
+    let mut bags_to_visit = bag
+        .bags
+        .iter()
+        .map(|b| (b.0, b.1.clone()))
+        .collect::<Vec<(i32, String)>>();
+    while let Some((num, other_color)) = bags_to_visit.pop() {
+        count += num;
+        let idx = bags.iter().position(|b| b.color == other_color);
+        if let Some(idx) = idx {
+            let other_bag = &bags[idx];
+            if !other_bag.bags.is_empty() {
+                bags_to_visit.extend(other_bag.bags.iter().map(|b| (b.0 * num, b.1.clone())));
+            }
+        }
+    }
+    Ok(count.into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 7,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 600,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
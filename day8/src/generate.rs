@@ -0,0 +1,58 @@
+use common::{Answer, Generated, Rng};
+
+/// Generates boot code of `size` instructions where exactly one `nop` has
+/// been corrupted into a `jmp`.
+///
+/// Up to the corrupted instruction the code runs straight through, with
+/// `nop`s that would jump backwards into it if changed. The corrupted `jmp`
+/// also jumps backwards, so the code loops. After it there are only forward
+/// jumps, so fixing it is the only way to make the code terminate. The answer
+/// to part 2 is known.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(4);
+    let corrupted = rng.range(size as u64 / 2, size as u64 - 2) as usize;
+    let mut program = Vec::new();
+    let mut acc = 0;
+    for idx in 0..corrupted {
+        if rng.chance(0.6) {
+            let value = rng.range(0, 100) as i64 - 50;
+            acc += value;
+            program.push(format!("acc {:+}", value));
+        } else {
+            let offset = -(rng.range(0, idx as u64) as i64);
+            program.push(format!("nop {:+}", offset));
+        }
+    }
+    let offset = rng.range(1, corrupted as u64) as i64;
+    program.push(format!("jmp {:+}", -offset));
+    // Instructions after the corrupted one, and which of them run once fixed
+    let mut pc = corrupted + 1;
+    for idx in corrupted + 1..size {
+        let runs = idx == pc;
+        if rng.chance(0.3) {
+            let offset = rng.range(1, (size - idx) as u64);
+            if runs {
+                pc += offset as usize;
+            }
+            program.push(format!("jmp {:+}", offset));
+        } else if rng.chance(0.5) {
+            let value = rng.range(0, 100) as i64 - 50;
+            if runs {
+                acc += value;
+                pc += 1;
+            }
+            program.push(format!("acc {:+}", value));
+        } else {
+            let offset = rng.range(0, 2 * size as u64) as i64 - size as i64;
+            if runs {
+                pc += 1;
+            }
+            program.push(format!("nop {:+}", offset));
+        }
+    }
+    Generated {
+        input: program.join("\n") + "\n",
+        part1: None,
+        part2: Some(Answer::from(acc)),
+    }
+}
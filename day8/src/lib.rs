@@ -0,0 +1,155 @@
+use common::{par, parse_field, Answer, Generator, ParseError, Puzzle};
+use std::convert::TryFrom;
+
+mod generate;
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+#[derive(Clone)]
+struct Machine {
+    program: Vec<Instruction>,
+    pc: i32,
+    acc: i32,
+}
+
+impl Machine {
+    fn new(program: Vec<Instruction>) -> Self {
+        Self {
+            program,
+            pc: 0,
+            acc: 0,
+        }
+    }
+
+    /// Runs the instruction at `pc`, or returns false if `pc` is outside the
+    /// program.
+    fn step(&mut self) -> bool {
+        let pc = match usize::try_from(self.pc) {
+            Ok(pc) if pc < self.program.len() => pc,
+            _ => return false,
+        };
+        match self.program[pc] {
+            Instruction::Nop(_) => {}
+            Instruction::Acc(a) => self.acc += a,
+            Instruction::Jmp(offset) => self.pc += offset - 1,
+        }
+        self.pc += 1;
+        true
+    }
+}
+
+fn load_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| {
+            let parts = l.split(' ').collect::<Vec<&str>>();
+            if parts.len() == 2 {
+                let inst = parts[0];
+                let num = parse_field(idx + 1, l, parts[1])?;
+                match inst {
+                    "nop" => Ok(Instruction::Nop(num)),
+                    "acc" => Ok(Instruction::Acc(num)),
+                    "jmp" => Ok(Instruction::Jmp(num)),
+                    _ => {
+                        let message = format!("unknown instruction '{}'", inst);
+                        Err(ParseError::at(idx + 1, l, inst, &message))
+                    }
+                }
+            } else {
+                Err(ParseError::new(
+                    idx + 1,
+                    1,
+                    l,
+                    "expected '<instruction> <offset>'",
+                ))
+            }
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(load_program(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    let program = load_program(input)?;
+    if program.is_empty() {
+        return Err(ParseError::new(1, 1, "", "empty program"));
+    }
+    let mut visited = vec![false; program.len()];
+    let mut machine = Machine::new(program);
+    let mut last = 0;
+    loop {
+        let pc = machine.pc as usize;
+        if machine.pc >= 0 && pc < visited.len() {
+            if visited[pc] {
+                break;
+            }
+            visited[pc] = true;
+            last = pc;
+        }
+        if !machine.step() {
+            // Point at the instruction that left the program
+            let line = input.lines().nth(last).unwrap_or("");
+            let message = "the program ends without repeating an instruction";
+            return Err(ParseError::new(last + 1, 1, line, message));
+        }
+    }
+    Ok(machine.acc.into())
+}
+
+// Runs the machine with the instruction at `idx` patched, returning the
+// accumulator if it terminates
+fn run_patched(machine: &Machine, idx: usize) -> Option<i32> {
+    let mut machine = machine.clone();
+    match machine.program[idx] {
+        Instruction::Nop(a) => machine.program[idx] = Instruction::Jmp(a),
+        Instruction::Acc(_) => return None,
+        Instruction::Jmp(a) => machine.program[idx] = Instruction::Nop(a),
+    }
+
+    let mut visited = vec![false; machine.program.len()];
+    loop {
+        let pc = machine.pc as usize;
+        if machine.pc >= 0 && pc < visited.len() {
+            if visited[pc] {
+                return None;
+            }
+            visited[pc] = true;
+        }
+        if !machine.step() {
+            return Some(machine.acc);
+        }
+    }
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    let program = load_program(input)?;
+    let machine = Machine::new(program);
+    let indices = (0..machine.program.len()).collect::<Vec<usize>>();
+    let acc = par::find_map_first(&indices, |idx| run_patched(&machine, *idx));
+    if let Some(acc) = acc {
+        Ok(acc.into())
+    } else {
+        Ok(Answer::NotFound)
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 8,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 600,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
@@ -0,0 +1,21 @@
+use common::Answer::Number;
+use day8::{solve_part1, solve_part2};
+
+#[test]
+fn leaving_the_program() {
+    let err = solve_part1("").unwrap_err();
+    assert_eq!((err.line, err.message.as_str()), (1, "empty program"));
+
+    let err = solve_part1("nop +0\njmp +1000\nacc +1").unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(
+        err.message,
+        "the program ends without repeating an instruction"
+    );
+    let err = solve_part1("acc +1\njmp -5").unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(solve_part1("acc +1\nacc +2").unwrap_err().line, 2);
+
+    assert_eq!(solve_part1("nop +0\nacc +3\njmp -2").unwrap(), Number(3));
+    assert_eq!(solve_part2("nop +0\nacc +3\njmp -2").unwrap(), Number(3));
+}
@@ -0,0 +1,57 @@
+use crate::PREAMBLE;
+use common::{Answer, Generated, Rng};
+
+// Numbers that are sums of two larger than zero double every 25 lines or so,
+// which would overflow after about 1500 lines. Instead every tenth number is
+// 0, the sum of two earlier zeros, and a sum that would be larger than this
+// is replaced by 0 plus one of the numbers.
+const MAX_NUMBER: u64 = 1 << 40;
+
+fn is_sum_of_two(window: &[u64], num: u64) -> bool {
+    window
+        .iter()
+        .enumerate()
+        .any(|(i, a)| window[i + 1..].iter().any(|b| a + b == num))
+}
+
+/// Generates `size` numbers for a preamble of 25, where only the last number
+/// isn't the sum of two of the 25 before it. The last number is the sum of a
+/// contiguous range early in the list. The answer to part 1 is known.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(PREAMBLE + 12);
+    let mut numbers = (1..=50).collect::<Vec<u64>>();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(PREAMBLE);
+    // Zeros at every tenth line, so there are always two in the window
+    for idx in (0..PREAMBLE).step_by(10) {
+        numbers[idx] = 0;
+    }
+    while numbers.len() < size - 1 {
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        let next = if numbers.len() % 10 == 0 {
+            0
+        } else {
+            let first = rng.index(PREAMBLE - 1);
+            let second = rng.range(first as u64 + 1, PREAMBLE as u64 - 1) as usize;
+            match window[first] + window[second] {
+                sum if sum <= MAX_NUMBER => sum,
+                _ => window[rng.index(PREAMBLE)],
+            }
+        };
+        numbers.push(next);
+    }
+    let invalid = loop {
+        let start = rng.index(size / 2);
+        let len = rng.range(2, 10) as usize;
+        let sum = numbers[start..start + len].iter().sum::<u64>();
+        if !is_sum_of_two(&numbers[numbers.len() - PREAMBLE..], sum) {
+            break sum;
+        }
+    };
+    numbers.push(invalid);
+    Generated {
+        input: numbers.iter().map(|n| format!("{}\n", n)).collect(),
+        part1: Some(Answer::from(invalid)),
+        part2: None,
+    }
+}
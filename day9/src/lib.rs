@@ -0,0 +1,129 @@
+use common::{parse_field, Answer, Generator, ParseError, Puzzle};
+
+mod generate;
+
+// The preamble is 5 for example.txt
+pub const PREAMBLE: usize = 25; // input.txt
+
+fn read_numbers(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_field(idx + 1, line, line))
+        .collect()
+}
+
+fn add_to_buffer(buffer: &mut [u64], num: u64) {
+    for i in 0..buffer.len() - 1 {
+        buffer[i] = buffer[i + 1];
+    }
+    buffer[buffer.len() - 1] = num;
+}
+
+fn is_last_valid(buffer: &[u64]) -> bool {
+    let last = buffer[buffer.len() - 1];
+    for i in 0..buffer.len() - 2 {
+        let numi = buffer[i];
+        if numi > last {
+            continue;
+        }
+        let to_be_found = last - numi;
+        if buffer[i + 1..buffer.len() - 1].contains(&to_be_found) {
+            return true;
+        }
+    }
+    false
+}
+
+fn find_first_invalid(numbers: &[u64], preamble: usize) -> Option<u64> {
+    let bufsize = preamble + 1;
+    let mut buffer = vec![0; bufsize];
+    for num in numbers.iter().take(bufsize) {
+        add_to_buffer(&mut buffer, *num);
+    }
+    for &num in numbers.iter().skip(bufsize) {
+        add_to_buffer(&mut buffer, num);
+        if !is_last_valid(&buffer) {
+            return Some(num);
+        }
+    }
+    None
+}
+
+// Exclusive end
+fn find_sum_range(numbers: &[u64], num_to_find: u64) -> Option<(usize, usize)> {
+    for start in 0..numbers.len() {
+        let mut sum = 0;
+        for (end, num) in numbers.iter().enumerate().skip(start) {
+            sum += num;
+            if sum > num_to_find {
+                break;
+            } else if sum == num_to_find {
+                return Some((start, end + 1));
+            }
+        }
+    }
+    None
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_numbers(input)?.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+    solve_part1_with_preamble(input, PREAMBLE)
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+    solve_part2_with_preamble(input, PREAMBLE)
+}
+
+/// Reads the numbers, failing if the preamble is too short for any number to
+/// be the sum of two before it.
+fn read_with_preamble(input: &str, preamble: usize) -> Result<Vec<u64>, ParseError> {
+    let numbers = read_numbers(input)?;
+    if preamble < 2 {
+        let line = input.lines().next().unwrap_or("");
+        let message = format!("a preamble of {} can't hold two numbers", preamble);
+        return Err(ParseError::new(1, 1, line, &message));
+    }
+    Ok(numbers)
+}
+
+pub fn solve_part1_with_preamble(input: &str, preamble: usize) -> Result<Answer, ParseError> {
+    let numbers = read_with_preamble(input, preamble)?;
+    Ok(match find_first_invalid(&numbers, preamble) {
+        Some(first_invalid) => first_invalid.into(),
+        None => Answer::NotFound,
+    })
+}
+
+pub fn solve_part2_with_preamble(input: &str, preamble: usize) -> Result<Answer, ParseError> {
+    let numbers = read_with_preamble(input, preamble)?;
+    let range = match find_first_invalid(&numbers, preamble)
+        .and_then(|first_invalid| find_sum_range(&numbers, first_invalid))
+    {
+        Some(range) => range,
+        None => return Ok(Answer::NotFound),
+    };
+    let mut min = numbers[range.0];
+    let mut max = numbers[range.0];
+    for num in numbers[range.0..range.1].iter() {
+        min = min.min(*num);
+        max = max.max(*num);
+    }
+    Ok((min + max).into())
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    day: 9,
+    parse,
+    builtin_input: None,
+    part1: solve_part1,
+    part2: solve_part2,
+    generator: Generator {
+        default_size: 1000,
+        max_size: None,
+        generate: generate::generate,
+    },
+};
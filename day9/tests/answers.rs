@@ -0,0 +1,42 @@
+use common::testing::{check_answers, Expected};
+use common::Answer::Number;
+use day9::{solve_part1, solve_part1_with_preamble, solve_part2, solve_part2_with_preamble};
+
+const EXPECTED_EXAMPLE: &[Expected] = &[Expected {
+    name: "example.txt",
+    input: include_str!("../example.txt"),
+    part1: Some(Number(127)),
+    part2: Some(Number(62)),
+}];
+
+const EXPECTED: &[Expected] = &[Expected {
+    name: "input.txt",
+    input: include_str!("../input.txt"),
+    part1: Some(Number(756008079)),
+    part2: Some(Number(93727241)),
+}];
+
+#[test]
+fn example_answers() {
+    check_answers(
+        EXPECTED_EXAMPLE,
+        |input| solve_part1_with_preamble(input, 5),
+        |input| solve_part2_with_preamble(input, 5),
+    );
+}
+
+#[test]
+fn answers() {
+    check_answers(EXPECTED, solve_part1, solve_part2);
+}
+
+#[test]
+fn short_preambles() {
+    let input = include_str!("../example.txt");
+    for preamble in 0..2 {
+        assert!(solve_part1_with_preamble(input, preamble).is_err());
+        let err = solve_part2_with_preamble(input, preamble).unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+    assert!(solve_part1_with_preamble(input, 2).is_ok());
+}
@@ -0,0 +1,330 @@
+use common::ParseError;
+use std::fmt;
+
+/// A rectangular grid of cells stored row by row. Positions are `(x, y)`
+/// with `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn column(&self, x: usize) -> Vec<T> {
+        (0..self.height).map(|y| self.row(y)[x].clone()).collect()
+    }
+
+    /// Returns the grid rotated a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for x in 0..self.width {
+            for y in (0..self.height).rev() {
+                cells.push(self.row(y)[x].clone());
+            }
+        }
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Returns the grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+        Self { cells, ..*self }
+    }
+
+    /// Returns the grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let cells = (0..self.height)
+            .rev()
+            .flat_map(|y| self.row(y).iter().cloned())
+            .collect();
+        Self { cells, ..*self }
+    }
+
+    /// Returns one of the 8 orientations of the grid. Orientations 4 to 7 are
+    /// mirrored left to right, and all are then rotated clockwise
+    /// `orientation % 4` times.
+    pub fn orientation(&self, orientation: usize) -> Self {
+        let mut grid = if orientation >= 4 {
+            self.flip_horizontal()
+        } else {
+            self.clone()
+        };
+        for _ in 0..orientation % 4 {
+            grid = grid.rotate_right();
+        }
+        grid
+    }
+
+    /// Returns the part of the grid with its top left corner at `(x, y)`.
+    pub fn sub_grid(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let cells = (y..y + height)
+            .flat_map(|y| self.row(y)[x..x + width].iter().cloned())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Copies `other` into the grid with its top left corner at `(x, y)`.
+    pub fn paste(&mut self, x: usize, y: usize, other: &Grid<T>) {
+        for (oy, row) in other.rows().enumerate() {
+            let start = (y + oy) * self.width + x;
+            self.cells[start..start + other.width].clone_from_slice(row);
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == width));
+        Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` outside the grid.
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.index(x, y).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        self.index(x, y).map(move |idx| &mut self.cells[idx])
+    }
+
+    /// Returns the cell at `(x, y)` with the grid repeating in all
+    /// directions. The grid must not be empty.
+    pub fn get_wrapping(&self, x: i64, y: i64) -> &T {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// Sets the cell at `(x, y)`. Returns false if it's outside the grid.
+    pub fn set(&mut self, x: i64, y: i64, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and an empty grid has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over all cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (((idx % width) as i64, (idx / width) as i64), cell))
+    }
+
+    /// Iterates over the positions of the up to 8 cells around `(x, y)` that
+    /// are inside the grid.
+    pub fn neighbors(&self, x: i64, y: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        neighbor_offsets::<2>()
+            .into_iter()
+            .map(move |[dx, dy]| (x + dx, y + dy))
+            .filter(move |&(x, y)| self.index(x, y).is_some())
+    }
+
+    /// Like `neighbors`, but only the up to 4 cells sharing an edge with
+    /// `(x, y)`.
+    pub fn orthogonal_neighbors(&self, x: i64, y: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.neighbors(x, y)
+            .filter(move |&(nx, ny)| nx == x || ny == y)
+    }
+
+    /// Renders the grid with one character per cell and a newline after each
+    /// row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(&cell));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row.iter() {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the offsets to all neighbors of a point in `N` dimensions, that is
+/// every combination of -1, 0 and 1 except all zeros, with the first
+/// coordinate changing fastest.
+pub fn neighbor_offsets<const N: usize>() -> Vec<[i64; N]> {
+    let mut offsets = vec![[0; N]];
+    for dim in (0..N).rev() {
+        offsets = offsets
+            .iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |d| {
+                    let mut offset = *offset;
+                    offset[dim] = d;
+                    offset
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|offset| offset.iter().any(|d| *d != 0));
+    offsets
+}
+
+/// Parses a grid of `#` and `.` characters.
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    parse_with(
+        input,
+        1,
+        |c| if c == '#' || c == '.' { Some(c) } else { None },
+    )
+}
+
+/// Parses a grid with one cell per character, where `cell` returns `None`
+/// for invalid characters. `first_line` is the line number of the first row
+/// in error messages.
+pub fn parse_with<T>(
+    input: &str,
+    first_line: usize,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for (idx, l) in input.lines().enumerate() {
+        let line_no = first_line + idx;
+        let mut row = Vec::new();
+        for (col, c) in l.chars().enumerate() {
+            match cell(c) {
+                Some(value) => row.push(value),
+                None => {
+                    let message = format!("unexpected character '{}'", c);
+                    return Err(ParseError::new(line_no, col + 1, l, &message));
+                }
+            }
+        }
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let message = format!("expected {} columns, found {}", first.len(), row.len());
+                return Err(ParseError::new(line_no, 1, l, &message));
+            }
+        } else if row.is_empty() {
+            return Err(ParseError::new(line_no, 1, l, "empty line"));
+        }
+        rows.push(row);
+    }
+    Ok(Grid::from_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        parse(text).unwrap()
+    }
+
+    #[test]
+    fn access() {
+        let g = grid("#..\n.#.\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get(1, 1), Some(&'#'));
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.get(-1, 0), None);
+        assert_eq!(g.get_wrapping(4, 3), &'#');
+        assert_eq!(g.get_wrapping(-3, -2), &'#');
+        assert_eq!(g.row(1), &['.', '#', '.']);
+        assert_eq!(g.column(0), vec!['#', '.']);
+    }
+
+    #[test]
+    fn neighbors() {
+        let g = grid("...\n...\n...\n");
+        assert_eq!(g.neighbors(1, 1).count(), 8);
+        assert_eq!(
+            g.neighbors(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(g.orthogonal_neighbors(0, 0).count(), 2);
+        assert_eq!(neighbor_offsets::<4>().len(), 80);
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("#..\n##.\n");
+        assert_eq!(g.rotate_right().to_string(), "##\n#.\n..\n");
+        assert_eq!(g.flip_horizontal().to_string(), "..#\n.##\n");
+        assert_eq!(g.flip_vertical().to_string(), "##.\n#..\n");
+        assert_eq!(g.orientation(4), g.flip_horizontal());
+        assert_eq!(g.orientation(2), g.flip_horizontal().flip_vertical());
+        assert_eq!(g.sub_grid(1, 0, 2, 2).to_string(), "..\n#.\n");
+        let mut big = Grid::new(4, 3, '.');
+        big.paste(1, 1, &g);
+        assert_eq!(big.render(|c| *c), "....\n.#..\n.##.\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("#.\n#x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "unexpected character 'x'");
+        let err = parse("#.\n#\n").unwrap_err();
+        assert_eq!(err.message, "expected 2 columns, found 1");
+        assert_eq!(parse("\n").unwrap_err().message, "empty line");
+    }
+}